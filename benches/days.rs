@@ -0,0 +1,35 @@
+//! Benchmarks every scaffolded day under `cargo bench`, see `template::commands::bench`.
+use advent_of_code::template::{
+    commands::bench::{bench_handler, Options},
+    selection::{parse_days, Selection},
+};
+use std::process;
+
+fn parse_args() -> Result<Options, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+
+    // passed by `cargo bench` to every bench target.
+    args.contains("--bench");
+
+    Ok(Options {
+        selection: Selection {
+            days: args.opt_value_from_fn("--days", parse_days)?,
+            skip: args
+                .opt_value_from_fn("--skip", parse_days)?
+                .unwrap_or_default(),
+            ..Selection::default()
+        },
+        part: args.opt_value_from_str("--part")?,
+        csv: args.opt_value_from_str("--csv")?,
+    })
+}
+
+fn main() {
+    match parse_args() {
+        Ok(options) => bench_handler(options),
+        Err(e) => {
+            eprintln!("Failed to parse arguments: {}", e);
+            process::exit(1);
+        }
+    }
+}
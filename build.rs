@@ -0,0 +1,122 @@
+/// Expands the example manifests in `data/examples/DD.toml` into one test per example and part, and the known
+/// answers in `data/answers/DD.toml` into one test per part on the real input. Each day gets `$OUT_DIR/examples_D.rs`
+/// and `$OUT_DIR/inputs_D.rs`, which `advent_of_code::example_tests!(D)` and `advent_of_code::tests!` include.
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+#[allow(dead_code)]
+#[path = "src/template/toml.rs"]
+mod toml;
+
+const PARTS: [(&str, u8); 2] = [("part_one", 1), ("part_two", 2)];
+
+fn to_ident(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn generate_tests(day: u8, manifest: &str) -> String {
+    let doc = match toml::parse(manifest) {
+        Ok(doc) => doc,
+        Err(e) => panic!("data/examples/{:02}.toml: {}", day, e),
+    };
+
+    let root = doc.root().cloned().unwrap_or_default();
+    let examples = std::iter::once(("example", &root)).chain(
+        doc.sections()
+            .filter(|s| s.name != "params" && !s.name.contains('.'))
+            .map(|s| (s.name.as_str(), s)),
+    );
+
+    let mut out = String::new();
+
+    for (name, table) in examples {
+        for (part_name, part) in PARTS {
+            if table.get(part_name).is_none() {
+                continue;
+            }
+
+            out.push_str(&format!(
+                "#[test]\nfn {part_name}_{ident}() {{\n    advent_of_code::template::examples::check({day}, {name:?}, {part}, |input, example| __run_example({part_name}, advent_of_code::parse::unwrap(parse(input)), &example.params));\n}}\n\n",
+                ident = to_ident(name),
+            ));
+        }
+    }
+
+    out
+}
+
+fn generate_input_tests(day: u8, answers: &str) -> String {
+    let doc = match toml::parse(answers) {
+        Ok(doc) => doc,
+        Err(e) => panic!("data/answers/{:02}.toml: {}", day, e),
+    };
+
+    let root = doc.root().cloned().unwrap_or_default();
+    let mut out = String::new();
+
+    for (part_name, part) in PARTS {
+        if root.get(part_name).is_none() {
+            continue;
+        }
+
+        out.push_str(&format!(
+            "#[test]\n#[ignore = \"needs data/inputs/{day:02}.txt\"]\nfn {part_name}_input() {{\n    advent_of_code::template::examples::check_real_input({day}, {part}, |input| __run_example({part_name}, advent_of_code::parse::unwrap(parse(input)), &[]));\n}}\n\n",
+        ));
+    }
+
+    out
+}
+
+/// The data directory the days read at runtime, see `input::get_data_dir`.
+fn data_dir() -> PathBuf {
+    env::var_os("AOC_DATA_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("data"))
+}
+
+fn main() {
+    let data_dir = data_dir();
+
+    println!("cargo:rerun-if-env-changed=AOC_DATA_DIR");
+    println!(
+        "cargo:rerun-if-changed={}",
+        data_dir.join("examples").display()
+    );
+    println!(
+        "cargo:rerun-if-changed={}",
+        data_dir.join("answers").display()
+    );
+    println!("cargo:rerun-if-changed=src/template/toml.rs");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+
+    for day in 1..=25 {
+        let manifest = fs::read_to_string(data_dir.join(format!("examples/{:02}.toml", day)))
+            .unwrap_or_default();
+        let tests = generate_tests(day, &manifest);
+        fs::write(
+            Path::new(&out_dir).join(format!("examples_{}.rs", day)),
+            tests,
+        )
+        .unwrap();
+
+        let answers = fs::read_to_string(data_dir.join(format!("answers/{:02}.toml", day)))
+            .unwrap_or_default();
+        let tests = generate_input_tests(day, &answers);
+        fs::write(
+            Path::new(&out_dir).join(format!("inputs_{}.rs", day)),
+            tests,
+        )
+        .unwrap();
+    }
+}
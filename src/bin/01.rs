@@ -1,11 +1,17 @@
+use advent_of_code::parse::{number, ParseError};
 use itertools::sorted;
 
 type Input = Vec<u32>;
 
-fn parse(input: &str) -> Input {
+fn parse(input: &str) -> Result<Input, ParseError> {
     input
         .split("\n\n")
-        .map(|c| c.lines().filter_map(|l| l.parse::<u32>().ok()).sum())
+        .map(|c| {
+            c.lines()
+                .filter(|l| !l.is_empty())
+                .map(|l| number::<u32>(input, l))
+                .sum()
+        })
         .collect()
 }
 
@@ -19,19 +25,8 @@ pub fn part_two(input: Input) -> Option<u32> {
 
 advent_of_code::main!(1);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(parse(&advent_of_code::template::read_file("examples", 1)));
-        assert_eq!(result, Some(24000));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(parse(&advent_of_code::template::read_file("examples", 1)));
-        assert_eq!(result, Some(45000));
-    }
+advent_of_code::tests! {
+    day: 1,
+    part_one: Some(24000),
+    part_two: Some(45000),
 }
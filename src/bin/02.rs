@@ -1,14 +1,15 @@
+use advent_of_code::ParseError;
+
 type Input = Vec<(i8, i8)>;
 
-fn parse(input: &str) -> Input {
+fn parse(input: &str) -> Result<Input, ParseError> {
     input
         .lines()
-        .filter_map(|l| {
-            let byte = l.as_bytes();
-            Some((
-                (byte.first()? - 'A' as u8) as i8,
-                (byte.last()? - 'X' as u8) as i8,
-            ))
+        .map(|l| match l.as_bytes() {
+            [their @ b'A'..=b'C', b' ', mine @ b'X'..=b'Z'] => {
+                Ok(((their - b'A') as i8, (mine - b'X') as i8))
+            }
+            _ => Err(ParseError::at(input, l, "a line like \"A Y\"")),
         })
         .collect()
 }
@@ -45,19 +46,8 @@ pub fn part_two(input: Input) -> Option<u32> {
 
 advent_of_code::main!(2);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(parse(&advent_of_code::template::read_file("examples", 2)));
-        assert_eq!(result, Some(15));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(parse(&advent_of_code::template::read_file("examples", 2)));
-        assert_eq!(result, Some(12));
-    }
+advent_of_code::tests! {
+    day: 2,
+    part_one: Some(15),
+    part_two: Some(12),
 }
@@ -1,7 +1,17 @@
+use advent_of_code::ParseError;
+
 type Input<'a> = Vec<&'a str>;
 
-fn parse(input: &str) -> Input {
-    input.lines().collect()
+fn parse(input: &str) -> Result<Input, ParseError> {
+    input
+        .lines()
+        .map(
+            |l| match l.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+                Some((i, c)) => Err(ParseError::at(input, &l[i..i + c.len_utf8()], "a letter")),
+                None => Ok(l),
+            },
+        )
+        .collect()
 }
 
 fn priority_func(input: u8) -> u8 {
@@ -43,19 +53,8 @@ pub fn part_two(input: Input) -> Option<u32> {
 
 advent_of_code::main!(3);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(parse(&advent_of_code::template::read_file("examples", 3)));
-        assert_eq!(result, Some(157));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(parse(&advent_of_code::template::read_file("examples", 3)));
-        assert_eq!(result, Some(70));
-    }
+advent_of_code::tests! {
+    day: 3,
+    part_one: Some(157),
+    part_two: Some(70),
 }
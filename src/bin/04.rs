@@ -1,3 +1,4 @@
+use advent_of_code::parse::{number, split_once, ParseError};
 use std::cmp::{max, min};
 use std::ops::Range;
 
@@ -11,19 +12,19 @@ fn overlaps(v1: &Range<u8>, v2: &Range<u8>) -> bool {
     max(v1.start, v2.start) <= min(v1.end, v2.end)
 }
 
-fn parse(input: &str) -> Input {
+fn parse(input: &str) -> Result<Input, ParseError> {
+    let range = |s| -> Result<Range<u8>, ParseError> {
+        let (start, end) = split_once(input, s, "-")?;
+        Ok(number(input, start)?..number(input, end)?)
+    };
+
     input
         .lines()
         .map(|l| {
-            let (part1, part2) = l.split_once(",").unwrap();
-            let (s1, e1) = part1.split_once("-").unwrap();
-            let (s2, e2) = part2.split_once("-").unwrap();
-            (
-                (s1.parse().unwrap()..e1.parse().unwrap()),
-                (s2.parse().unwrap()..e2.parse().unwrap()),
-            )
+            let (part1, part2) = split_once(input, l, ",")?;
+            Ok((range(part1)?, range(part2)?))
         })
-        .collect::<Input>()
+        .collect()
 }
 
 pub fn part_one(input: Input) -> Option<usize> {
@@ -41,19 +42,8 @@ pub fn part_two(input: Input) -> Option<usize> {
 
 advent_of_code::main!(4);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(parse(&advent_of_code::template::read_file("examples", 4)));
-        assert_eq!(result, Some(2));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(parse(&advent_of_code::template::read_file("examples", 4)));
-        assert_eq!(result, Some(4));
-    }
+advent_of_code::tests! {
+    day: 4,
+    part_one: Some(2),
+    part_two: Some(4),
 }
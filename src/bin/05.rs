@@ -1,3 +1,4 @@
+use advent_of_code::parse::{next, number, ParseError};
 use itertools::Itertools;
 
 type Input = (Stack, Vec<Instruction>);
@@ -21,35 +22,53 @@ fn top_row_string(stack: Stack) -> String {
     stack.iter().filter_map(|pillar| pillar.last()).join("")
 }
 
-fn parse(input: &str) -> Input {
-    let (stack_input, instruction_input) = input.split_once("\n\n").unwrap();
+fn parse(input: &str) -> Result<Input, ParseError> {
+    let (stack_input, instruction_input) = input.split_once("\n\n").ok_or_else(|| {
+        ParseError::after(input, input.trim_end(), "a blank line before the moves")
+    })?;
     let mut stack_iter = stack_input.lines().rev();
-    let mut stack = vec![vec![]; stack_iter.next().unwrap().len() / 4 + 1];
+    let numbers = stack_iter
+        .next()
+        .ok_or_else(|| ParseError::at_offset(input, 0, 0, "the stacks"))?;
+    let mut stack = vec![vec![]; numbers.len() / 4 + 1];
 
-    stack_iter.for_each(|line| {
-        line.chars()
-            .skip(1)
-            .step_by(4)
-            .enumerate()
-            .for_each(|(i, x)| {
-                if x != ' ' {
-                    stack[i].push(x)
-                }
-            })
-    });
+    for line in stack_iter {
+        for (i, (pos, x)) in line.char_indices().skip(1).step_by(4).enumerate() {
+            if x != ' ' {
+                stack
+                    .get_mut(i)
+                    .ok_or_else(|| {
+                        let crate_str = &line[pos..pos + x.len_utf8()];
+                        ParseError::at(input, crate_str, "a crate above a stack number")
+                    })?
+                    .push(x)
+            }
+        }
+    }
 
+    let stack_count = stack.len();
     let instructions = instruction_input
         .lines()
-        .map(|x| {
-            let k = x.split_ascii_whitespace().collect::<Vec<&str>>();
-            (
-                k[1].parse().unwrap(),
-                k[3].parse().unwrap(),
-                k[5].parse().unwrap(),
-            )
+        .map(|line| {
+            let mut fields = line.split_ascii_whitespace().skip(1).step_by(2);
+            let mut field = || next(input, line, &mut fields, "a number");
+            let count = number(input, field()?)?;
+            let mut stack_number = || -> Result<usize, ParseError> {
+                let token = field()?;
+                match number(input, token)? {
+                    n if (1..=stack_count).contains(&n) => Ok(n),
+                    _ => Err(ParseError::at(
+                        input,
+                        token,
+                        format!("a stack from 1 to {}", stack_count),
+                    )),
+                }
+            };
+            Ok((count, stack_number()?, stack_number()?))
         })
-        .collect();
-    (stack, instructions)
+        .collect::<Result<_, ParseError>>()?;
+
+    Ok((stack, instructions))
 }
 
 pub fn part_one(input: Input) -> Option<String> {
@@ -66,19 +85,8 @@ pub fn part_two(input: Input) -> Option<String> {
 
 advent_of_code::main!(5);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(parse(&advent_of_code::template::read_file("examples", 5)));
-        assert_eq!(result, Some("CMZ".to_string()));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(parse(&advent_of_code::template::read_file("examples", 5)));
-        assert_eq!(result, Some("MCD".to_string()));
-    }
+advent_of_code::tests! {
+    day: 5,
+    part_one: Some("CMZ".to_string()),
+    part_two: Some("MCD".to_string()),
 }
@@ -1,3 +1,4 @@
+use advent_of_code::ParseError;
 use itertools::Itertools;
 
 type Input = Vec<char>;
@@ -18,8 +19,16 @@ fn first_marker(input: Input, windows_size: usize) -> Option<usize> {
         .map(|(pos, _)| pos + windows_size)
 }
 
-fn parse(input: &str) -> Input {
-    input.trim().chars().collect()
+fn parse(input: &str) -> Result<Input, ParseError> {
+    let signal = input.trim();
+    match signal.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+        Some((i, c)) => Err(ParseError::at(
+            input,
+            &signal[i..i + c.len_utf8()],
+            "a lowercase letter",
+        )),
+        None => Ok(signal.chars().collect()),
+    }
 }
 
 pub fn part_one(input: Input) -> Option<usize> {
@@ -32,19 +41,8 @@ pub fn part_two(input: Input) -> Option<usize> {
 
 advent_of_code::main!(6);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(parse(&advent_of_code::template::read_file("examples", 6)));
-        assert_eq!(result, Some(7));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(parse(&advent_of_code::template::read_file("examples", 6)));
-        assert_eq!(result, Some(19));
-    }
+advent_of_code::tests! {
+    day: 6,
+    part_one: Some(7),
+    part_two: Some(19),
 }
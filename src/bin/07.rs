@@ -1,3 +1,4 @@
+use advent_of_code::parse::{number, split_once, ParseError};
 use indextree::{Arena, NodeId};
 
 #[derive(Clone)]
@@ -8,40 +9,51 @@ pub struct Folder<'a> {
 
 type Input<'a> = Arena<Folder<'a>>;
 
-fn parse(input: &str) -> Input {
+fn parse(input: &str) -> Result<Input, ParseError> {
     let mut file_tree = Arena::new();
     let mut current_file = file_tree.new_node(Folder { name: "/", size: 0 });
-    input.split("$ ").skip(2).for_each(|pair| {
-        let (cmd_part, result_part) = pair.split_at(2);
+
+    for pair in input.split("$ ").skip(2) {
+        let (cmd_part, result_part) = match (pair.get(..2), pair.get(2..)) {
+            (Some(cmd_part), Some(result_part)) => (cmd_part, result_part),
+            _ => return Err(ParseError::at(input, pair, "a command")),
+        };
         let trimmed = result_part.trim();
+
         match (cmd_part, trimmed) {
-            ("cd", "..") => current_file = file_tree.get(current_file).unwrap().parent().unwrap(),
+            ("cd", "..") => {
+                current_file = file_tree
+                    .get(current_file)
+                    .and_then(|node| node.parent())
+                    .ok_or_else(|| ParseError::at(input, trimmed, "a directory below /"))?
+            }
             ("cd", _) => {
                 current_file = current_file
                     .children(&file_tree)
                     .find(|&x| file_tree.get(x).unwrap().get().name == trimmed)
-                    .unwrap()
+                    .ok_or_else(|| ParseError::at(input, trimmed, "a directory listed by ls"))?
             }
-            ("ls", _) => trimmed.lines().for_each(|line| {
-                let (size, name) = line.split_once(" ").unwrap();
-                if size == "dir" {
-                    current_file
-                        .append(file_tree.new_node(Folder { name, size: 0 }), &mut file_tree);
-                } else {
-                    current_file
-                        .ancestors(&file_tree)
-                        .collect::<Vec<NodeId>>()
-                        .into_iter()
-                        .for_each(|x| {
-                            file_tree.get_mut(x).unwrap().get_mut().size +=
-                                size.parse::<u32>().unwrap()
-                        })
+            ("ls", _) => {
+                for line in trimmed.lines() {
+                    let (size, name) = split_once(input, line, " ")?;
+                    if size == "dir" {
+                        current_file
+                            .append(file_tree.new_node(Folder { name, size: 0 }), &mut file_tree);
+                    } else {
+                        let size: u32 = number(input, size)?;
+                        current_file
+                            .ancestors(&file_tree)
+                            .collect::<Vec<NodeId>>()
+                            .into_iter()
+                            .for_each(|x| file_tree.get_mut(x).unwrap().get_mut().size += size)
+                    }
                 }
-            }),
-            _ => unreachable!(),
+            }
+            _ => return Err(ParseError::at(input, cmd_part, "\"cd\" or \"ls\"")),
         }
-    });
-    file_tree
+    }
+
+    Ok(file_tree)
 }
 
 pub fn part_one(input: Input) -> Option<u32> {
@@ -63,19 +75,8 @@ pub fn part_two(input: Input) -> Option<u32> {
 
 advent_of_code::main!(7);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(parse(&advent_of_code::template::read_file("examples", 7)));
-        assert_eq!(result, Some(95437));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(parse(&advent_of_code::template::read_file("examples", 7)));
-        assert_eq!(result, Some(24933642));
-    }
+advent_of_code::tests! {
+    day: 7,
+    part_one: Some(95437),
+    part_two: Some(24933642),
 }
@@ -1,4 +1,4 @@
-use advent_of_code::{Direction, Point, SimpleGrid};
+use advent_of_code::{Direction, ParseError, Point, SimpleGrid};
 use itertools::Itertools;
 
 type Input<'a> = SimpleGrid<u32>;
@@ -10,8 +10,8 @@ static FOUR_DIRECTIONS: [Direction; 4] = [
     Direction::West,
 ];
 
-fn parse(input: &str) -> Input {
-    SimpleGrid::from_str(input, &mut |c, _, _| c.to_digit(10).unwrap())
+fn parse(input: &str) -> Result<Input, ParseError> {
+    SimpleGrid::try_from_str(input, &mut |c, _, _| c.to_digit(10), "a digit")
 }
 
 pub fn part_one(input: Input) -> Option<usize> {
@@ -61,19 +61,8 @@ pub fn part_two(input: Input) -> Option<u32> {
 
 advent_of_code::main!(8);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(parse(&advent_of_code::template::read_file("examples", 8)));
-        assert_eq!(result, Some(21));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(parse(&advent_of_code::template::read_file("examples", 8)));
-        assert_eq!(result, Some(8));
-    }
+advent_of_code::tests! {
+    day: 8,
+    part_one: Some(21),
+    part_two: Some(8),
 }
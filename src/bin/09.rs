@@ -1,26 +1,25 @@
 use std::cmp::{max, min};
 
+use advent_of_code::parse::{number, split_once, ParseError};
 use advent_of_code::{Direction, Point, SparseGrid};
 
 type Input = Vec<(Direction, u32)>;
 
-fn parse(input: &str) -> Input {
+fn parse(input: &str) -> Result<Input, ParseError> {
     input
         .lines()
         .map(|line| {
-            let (dir, space) = line.split_once(" ").unwrap();
-            (
-                match dir {
-                    "U" => Direction::North,
-                    "R" => Direction::East,
-                    "D" => Direction::South,
-                    "L" => Direction::West,
-                    _ => unreachable!(),
-                },
-                space.parse::<u32>().unwrap(),
-            )
+            let (dir, space) = split_once(input, line, " ")?;
+            let direction = match dir {
+                "U" => Direction::North,
+                "R" => Direction::East,
+                "D" => Direction::South,
+                "L" => Direction::West,
+                _ => return Err(ParseError::at(input, dir, "U, R, D or L")),
+            };
+            Ok((direction, number(input, space)?))
         })
-        .collect::<Input>()
+        .collect()
 }
 
 fn knot_pos(head: &Point, knot: &Point) -> Point {
@@ -68,29 +67,4 @@ pub fn part_two(input: Input) -> Option<usize> {
 
 advent_of_code::main!(9);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(parse(
-            &advent_of_code::template::read_file("examples", 9)
-                .split_once("\n\n")
-                .unwrap()
-                .0,
-        ));
-        assert_eq!(result, Some(13));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(parse(
-            &advent_of_code::template::read_file("examples", 9)
-                .split_once("\n\n")
-                .unwrap()
-                .1,
-        ));
-        assert_eq!(result, Some(36));
-    }
-}
+advent_of_code::tests! { day: 9 }
@@ -1,3 +1,4 @@
+use advent_of_code::parse::{number, ParseError};
 use itertools::Itertools;
 
 type Input = Vec<i16>;
@@ -6,22 +7,23 @@ fn in_sprite_range(i: usize, x: i16) -> bool {
     (x - 1..=x + 1).contains(&((i % 40) as i16))
 }
 
-fn parse(input: &str) -> Input {
+fn parse(input: &str) -> Result<Input, ParseError> {
     let mut val = 1;
     let mut k = vec![1];
-    let mut other = input
-        .trim()
-        .split(|c| c == ' ' || c == '\n')
-        .filter_map(|x| {
-            if x == "" {
-                return None;
+
+    for line in input.lines().filter(|line| !line.is_empty()) {
+        match line.split_once(' ') {
+            None if line == "noop" => k.push(val),
+            Some(("addx", x)) => {
+                k.push(val);
+                val += number::<i16>(input, x)?;
+                k.push(val);
             }
-            val += x.parse::<i16>().unwrap_or(0);
-            Some(val)
-        })
-        .collect::<Vec<i16>>();
-    k.append(&mut other);
-    k
+            _ => return Err(ParseError::at(input, line, "\"noop\" or \"addx <number>\"")),
+        }
+    }
+
+    Ok(k)
 }
 
 pub fn part_one(input: Input) -> Option<i16> {
@@ -58,20 +60,8 @@ pub fn part_two(input: Input) -> Option<String> {
 
 advent_of_code::main!(10);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(parse(&advent_of_code::template::read_file("examples", 10)));
-        assert_eq!(result, Some(13140));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let expected = "▓▓░░▓▓░░▓▓░░▓▓░░▓▓░░▓▓░░▓▓░░▓▓░░▓▓░░▓▓░░\n▓▓▓░░░▓▓▓░░░▓▓▓░░░▓▓▓░░░▓▓▓░░░▓▓▓░░░▓▓▓░\n▓▓▓▓░░░░▓▓▓▓░░░░▓▓▓▓░░░░▓▓▓▓░░░░▓▓▓▓░░░░\n▓▓▓▓▓░░░░░▓▓▓▓▓░░░░░▓▓▓▓▓░░░░░▓▓▓▓▓░░░░░\n▓▓▓▓▓▓░░░░░░▓▓▓▓▓▓░░░░░░▓▓▓▓▓▓░░░░░░▓▓▓▓\n▓▓▓▓▓▓▓░░░░░░░▓▓▓▓▓▓▓░░░░░░░▓▓▓▓▓▓▓░░░░░";
-        let result = part_two(parse(&advent_of_code::template::read_file("examples", 10)));
-        assert_eq!(result, Some(expected.trim().to_string()));
-    }
+advent_of_code::tests! {
+    day: 10,
+    part_one: Some(13140),
+    part_two: Some("▓▓░░▓▓░░▓▓░░▓▓░░▓▓░░▓▓░░▓▓░░▓▓░░▓▓░░▓▓░░\n▓▓▓░░░▓▓▓░░░▓▓▓░░░▓▓▓░░░▓▓▓░░░▓▓▓░░░▓▓▓░\n▓▓▓▓░░░░▓▓▓▓░░░░▓▓▓▓░░░░▓▓▓▓░░░░▓▓▓▓░░░░\n▓▓▓▓▓░░░░░▓▓▓▓▓░░░░░▓▓▓▓▓░░░░░▓▓▓▓▓░░░░░\n▓▓▓▓▓▓░░░░░░▓▓▓▓▓▓░░░░░░▓▓▓▓▓▓░░░░░░▓▓▓▓\n▓▓▓▓▓▓▓░░░░░░░▓▓▓▓▓▓▓░░░░░░░▓▓▓▓▓▓▓░░░░░".to_string()),
 }
@@ -1,3 +1,5 @@
+use advent_of_code::parse::{number, split_once, strip_prefix, ParseError};
+
 type Input = Vec<Monkey>;
 // type Input = Vec<u32>;
 
@@ -27,47 +29,41 @@ pub struct Monkey {
     if_false: usize,
 }
 
-fn parse(input: &str) -> Input {
+fn parse(input: &str) -> Result<Input, ParseError> {
     input
         .split("\n\n")
-        .filter_map(|chunk| {
+        .filter(|chunk| !chunk.trim().is_empty())
+        .map(|chunk| {
             let pieces: Vec<&str> = chunk
                 .split(|x| x == ':' || x == '\n')
                 .map(|x| x.trim())
                 .collect();
-            if pieces.len() < 11 {
-                return None;
+            if pieces.len() < 12 {
+                return Err(ParseError::after(
+                    input,
+                    chunk.trim_end(),
+                    "a monkey described in six lines",
+                ));
             }
 
-            let op = pieces[5]
-                .strip_prefix("new = old ")
-                .unwrap()
-                .split_once(" ")
-                .unwrap();
-
-            Some(Monkey {
-                init_item: pieces[3].split(", ").map(|x| x.parse().unwrap()).collect(),
-                operation: match op {
-                    ("+", arg) => Op::Add(arg.parse().unwrap()),
+            let operation = strip_prefix(input, pieces[5], "new = old ")?;
+            let (op, arg) = split_once(input, operation, " ")?;
+            let target = |piece| number(input, strip_prefix(input, piece, "throw to monkey ")?);
+
+            Ok(Monkey {
+                init_item: pieces[3]
+                    .split(", ")
+                    .map(|x| number(input, x))
+                    .collect::<Result<_, _>>()?,
+                operation: match (op, arg) {
+                    ("+", arg) => Op::Add(number(input, arg)?),
                     ("*", "old") => Op::MultiSelf,
-                    ("*", arg) => Op::Multi(arg.parse().unwrap()),
-                    _ => unreachable!(),
+                    ("*", arg) => Op::Multi(number(input, arg)?),
+                    _ => return Err(ParseError::at(input, op, "\"+\" or \"*\"")),
                 },
-                divide_by: pieces[7]
-                    .strip_prefix("divisible by ")
-                    .unwrap()
-                    .parse()
-                    .unwrap(),
-                if_true: pieces[9]
-                    .strip_prefix("throw to monkey ")
-                    .unwrap()
-                    .parse()
-                    .unwrap(),
-                if_false: pieces[11]
-                    .strip_prefix("throw to monkey ")
-                    .unwrap()
-                    .parse()
-                    .unwrap(),
+                divide_by: number(input, strip_prefix(input, pieces[7], "divisible by ")?)?,
+                if_true: target(pieces[9])?,
+                if_false: target(pieces[11])?,
             })
         })
         .collect()
@@ -120,19 +116,8 @@ pub fn part_two(input: Input) -> Option<usize> {
 
 advent_of_code::main!(11);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(parse(&advent_of_code::template::read_file("examples", 11)));
-        assert_eq!(result, Some(10605));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(parse(&advent_of_code::template::read_file("examples", 11)));
-        assert_eq!(result, Some(2713310158));
-    }
+advent_of_code::tests! {
+    day: 11,
+    part_one: Some(10605),
+    part_two: Some(2713310158),
 }
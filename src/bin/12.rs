@@ -1,81 +1,79 @@
-use advent_of_code::shortest_path::shortest_path;
-use advent_of_code::{Point, SimpleGrid};
+use advent_of_code::{ParseError, Point, SimpleGrid};
+use std::collections::VecDeque;
 
 type Input = (SimpleGrid<char>, Point, Point);
 
-fn parse(input: &str) -> Input {
+fn parse(input: &str) -> Result<Input, ParseError> {
     let mut start: Option<Point> = None;
     let mut end: Option<Point> = None;
 
-    let grid: SimpleGrid<char> = SimpleGrid::from_str(input, &mut |c, x, y| match c {
-        'S' => {
-            start = Some(Point {
-                x: x as isize,
-                y: y as isize,
-            });
-            'a'
-        }
-        'E' => {
-            end = Some(Point {
-                x: x as isize,
-                y: y as isize,
-            });
-            'z'
+    let grid: SimpleGrid<char> = SimpleGrid::try_from_str(
+        input,
+        &mut |c, x, y| match c {
+            'S' => {
+                start = Some(Point {
+                    x: x as isize,
+                    y: y as isize,
+                });
+                Some('a')
+            }
+            'E' => {
+                end = Some(Point {
+                    x: x as isize,
+                    y: y as isize,
+                });
+                Some('z')
+            }
+            'a'..='z' => Some(c),
+            _ => None,
+        },
+        "a height from a to z, S or E",
+    )?;
+
+    let missing = |marker| ParseError::after(input, input.trim_end(), marker);
+    Ok((
+        grid,
+        start.ok_or_else(|| missing("a start marked S"))?,
+        end.ok_or_else(|| missing("an end marked E"))?,
+    ))
+}
+
+/// Walks the climb backwards from the end, so that one search yields the distance from every square.
+fn distances_to_end(grid: &SimpleGrid<char>, end: &Point) -> Vec<Option<usize>> {
+    let mut distances = vec![None; grid.width * grid.height];
+    let mut queue = VecDeque::from([(end.clone(), 0)]);
+    distances[grid.id_for_point(end)] = Some(0);
+
+    while let Some((point, distance)) = queue.pop_front() {
+        for neighbour in grid.cardianal_neighbours(&point) {
+            let id = grid.id_for_point(&neighbour);
+            let can_climb = (*grid.get(&point) as isize - *grid.get(&neighbour) as isize) < 2;
+
+            if can_climb && distances[id].is_none() {
+                distances[id] = Some(distance + 1);
+                queue.push_back((neighbour, distance + 1));
+            }
         }
-        c => c,
-    });
+    }
 
-    (grid, start.unwrap(), end.unwrap())
+    distances
 }
 
-pub fn part_one(input: Input) -> Option<usize> {
+pub fn solve(input: Input) -> (Option<usize>, Option<usize>) {
     let (grid, start, end) = input;
-    shortest_path(
-        &grid,
-        &vec![start],
-        &end,
-        |_| 1,
-        |a, b| (*grid.get(b) as isize - *grid.get(a) as isize) < 2,
-    )
-}
+    let distances = distances_to_end(&grid, &end);
 
-pub fn part_two(input: Input) -> Option<usize> {
-    let (grid, _start, end) = input;
-    let start_points: Vec<Point> = grid
+    let part_one = distances[grid.id_for_point(&start)];
+    let part_two = grid
         .points()
-        .into_iter()
-        .filter(|point| {
-            *grid.get(point) == 'a'
-                && grid
-                    .cardianal_neighbours(point)
-                    .iter()
-                    .any(|c| *grid.get(c) == 'b')
-        })
-        .collect();
-    shortest_path(
-        &grid,
-        &start_points,
-        &end,
-        |_| 1,
-        |a, b| (*grid.get(b) as isize - *grid.get(a) as isize) < 2,
-    )
-}
+        .iter()
+        .filter(|point| *grid.get(point) == 'a')
+        .filter_map(|point| distances[grid.id_for_point(point)])
+        .min();
 
-advent_of_code::main!(12);
+    (part_one, part_two)
+}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+advent_of_code::main!(12, solve);
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(parse(&advent_of_code::template::read_file("examples", 12)));
-        assert_eq!(result, Some(31));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(parse(&advent_of_code::template::read_file("examples", 12)));
-        assert_eq!(result, Some(29));
-    }
-}
+advent_of_code::tests! { day: 12 }
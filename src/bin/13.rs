@@ -1,3 +1,4 @@
+use advent_of_code::parse::{number, ParseError};
 use std::cmp::Ordering;
 use std::fmt::Debug;
 
@@ -10,53 +11,32 @@ pub enum Packet {
 type Input = Vec<Packet>;
 
 impl Packet {
-    fn parse(input: &str) -> Option<Packet> {
-        let mut packet = Vec::new();
-        let mut chars = input.chars();
-        let mut current = chars.next()?;
-        let mut is_array = false;
-        if current == '[' {
-            is_array = true;
-            current = chars.next()?;
-        }
-
-        while current != ']' {
-            if current == ',' {
-                current = chars.next()?;
-            }
-
-            if current.is_digit(10) {
-                let mut number = String::new();
-                while current.is_digit(10) {
-                    number.push(current);
-                    current = chars.next()?;
-                }
-                packet.push(Packet::Number(number.parse().unwrap()));
+    /// Parse the packet at the start of `s`, a slice of `input`. Returns the packet and the rest of `s`.
+    fn parse<'a>(input: &str, s: &'a str) -> Result<(Packet, &'a str), ParseError> {
+        let Some(mut rest) = s.strip_prefix('[') else {
+            let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+            if end == 0 {
+                return Err(ParseError::at(input, first_char(s), "a number or \"[\""));
             }
+            return Ok((Packet::Number(number(input, &s[..end])?), &s[end..]));
+        };
 
-            if current == '[' {
-                let mut array = String::new();
-                let mut depth = 1;
-                while depth > 0 {
-                    array.push(current);
-                    current = chars.next()?;
-                    if current == '[' {
-                        depth += 1;
-                    }
-                    if current == ']' {
-                        depth -= 1;
-                    }
-                }
-                array.push(current);
-                packet.push(Packet::parse(&array)?);
-                current = chars.next()?;
-            }
+        let mut packets = vec![];
+        if let Some(rest) = rest.strip_prefix(']') {
+            return Ok((Packet::Array(packets), rest));
         }
 
-        if is_array {
-            Some(Packet::Array(packet))
-        } else {
-            packet.pop()
+        loop {
+            let (packet, after) = Packet::parse(input, rest)?;
+            packets.push(packet);
+
+            if let Some(after) = after.strip_prefix(',') {
+                rest = after;
+            } else if let Some(after) = after.strip_prefix(']') {
+                return Ok((Packet::Array(packets), after));
+            } else {
+                return Err(ParseError::at(input, first_char(after), "\",\" or \"]\""));
+            }
         }
     }
 
@@ -136,8 +116,19 @@ impl Ord for Packet {
     }
 }
 
-fn parse(input: &str) -> Input {
-    input.lines().filter_map(Packet::parse).collect()
+fn first_char(s: &str) -> &str {
+    &s[..s.chars().next().map_or(0, char::len_utf8)]
+}
+
+fn parse(input: &str) -> Result<Input, ParseError> {
+    input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| match Packet::parse(input, line)? {
+            (packet, "") => Ok(packet),
+            (_, rest) => Err(ParseError::at(input, rest, "the end of the packet")),
+        })
+        .collect()
 }
 
 pub fn part_one(input: Input) -> Option<usize> {
@@ -183,19 +174,8 @@ pub fn part_two(mut input: Input) -> Option<usize> {
 
 advent_of_code::main!(13);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(parse(&advent_of_code::template::read_file("examples", 13)));
-        assert_eq!(result, Some(13));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(parse(&advent_of_code::template::read_file("examples", 13)));
-        assert_eq!(result, Some(140));
-    }
+advent_of_code::tests! {
+    day: 13,
+    part_one: Some(13),
+    part_two: Some(140),
 }
@@ -1,5 +1,6 @@
 use itertools::Itertools;
 
+use advent_of_code::parse::{number, split_once, ParseError};
 use advent_of_code::{Direction, Point, SparseGrid};
 
 #[derive(Clone, Eq, PartialEq)]
@@ -10,27 +11,42 @@ pub enum BlockerType {
 
 type Input = (SparseGrid<BlockerType>, isize);
 
-fn parse(input: &str) -> Input {
-    input
-        .lines()
-        .fold((SparseGrid::default(), 0), |mut acc, l| {
-            l.split(" -> ")
-                .filter_map(|segment| {
-                    let (x, y) = segment.split_once(',').unwrap();
-                    Some(Point {
-                        x: x.parse().ok()?,
-                        y: y.parse().ok()?,
-                    })
-                })
-                .tuple_windows()
-                .for_each(|(a, b)| {
-                    for point in a.line_to(&b) {
-                        acc.1 = acc.1.max(point.y);
-                        acc.0.insert(point, BlockerType::Wall);
-                    }
-                });
-            acc
-        })
+fn parse(input: &str) -> Result<Input, ParseError> {
+    let mut grid = SparseGrid::default();
+    let mut max_y = 0;
+
+    for l in input.lines() {
+        let points = l
+            .split(" -> ")
+            .map(|segment| {
+                let (x, y) = split_once(input, segment, ",")?;
+                // coordinates are limited to `u16`: puzzle inputs stay far below, and the bound keeps a corrupted
+                // input from drawing walls of billions of points or growing sand piles at negative positions.
+                let point = Point {
+                    x: number::<u16>(input, x)? as isize,
+                    y: number::<u16>(input, y)? as isize,
+                };
+                Ok((segment, point))
+            })
+            .collect::<Result<Vec<_>, ParseError>>()?;
+
+        for ((_, a), (segment, b)) in points.iter().tuple_windows() {
+            if a.x != b.x && a.y != b.y {
+                return Err(ParseError::at(
+                    input,
+                    segment,
+                    "a point in line with the previous one",
+                ));
+            }
+
+            for point in a.line_to(b) {
+                max_y = max_y.max(point.y);
+                grid.insert(point, BlockerType::Wall);
+            }
+        }
+    }
+
+    Ok((grid, max_y))
 }
 
 fn next_pos(grid: &SparseGrid<BlockerType>, point: &Point) -> Option<Point> {
@@ -71,9 +87,14 @@ pub fn part_one(mut input: Input) -> Option<u32> {
                 input.0.insert(current_pos.clone(), BlockerType::Sand);
                 path.pop();
                 count += 1;
+
+                if count % 10 == 0 {
+                    advent_of_code::viz!(viz::frame(&input.0, None));
+                }
             }
         }
     }
+    advent_of_code::viz!(viz::frame(&input.0, None));
     Some(count)
 }
 
@@ -94,58 +115,99 @@ pub fn part_two(mut input: Input) -> Option<u32> {
         input.0.insert(current_pos.clone(), BlockerType::Sand);
         count += 1;
 
+        if count % 250 == 0 {
+            advent_of_code::viz!(viz::frame(&input.0, Some(floor)));
+        }
+
         if *current_pos == (Point { x: 500, y: 0 }) {
             break;
         }
 
         path.pop();
     }
+    advent_of_code::viz!(viz::frame(&input.0, Some(floor)));
     Some(count)
 }
 
-// fn grid_string(grid: &SparseGrid<BlockerType>) -> String {
-//     let (min_x, max_x, min_y, max_y) = grid
-//         .points
-//         .keys()
-//         .fold((999999, 0, 999999, 0), |(min_x, max_x, min_y, max_y), point| {
-//             (
-//                 min(min_x, point.x),
-//                 max(max_x, point.x),
-//                 min(min_y, point.y),
-//                 max(max_y, point.y),
-//             )
-//         });
-//
-//     let mut result = String::new();
-//     for y in min_y..=max_y {
-//         for x in min_x..=max_x {
-//             let point = Point { x, y };
-//             result.push(match grid.get(&point) {
-//                 Some(BlockerType::Sand) => '░',
-//                 Some(BlockerType::Wall) => '█',
-//                 None => ' ',
-//             });
-//         }
-//         result.push('\n');
-//     }
-//     result
-// }
+/// Frames of the falling sand, see `template::viz`.
+#[cfg(feature = "viz")]
+mod viz {
+    use super::BlockerType;
+    use advent_of_code::template::viz::{Color, Frame};
+    use advent_of_code::{Point, SparseGrid};
+    use itertools::Itertools;
+
+    static PALETTE: &[Color] = &[[15, 15, 35], [110, 110, 120], [230, 190, 90]];
+
+    /// The cave around the rocks, or down to the floor of part two, which bounds the pile of sand.
+    pub fn frame(grid: &SparseGrid<BlockerType>, floor: Option<isize>) -> Frame {
+        let walls = grid
+            .points
+            .iter()
+            .filter(|(_, blocker)| **blocker == BlockerType::Wall)
+            .map(|(point, _)| point);
+
+        let (min, max) = match floor {
+            Some(floor) => (
+                Point {
+                    x: 500 - floor,
+                    y: 0,
+                },
+                Point {
+                    x: 500 + floor,
+                    y: floor,
+                },
+            ),
+            None => {
+                let (min_x, max_x) = walls
+                    .clone()
+                    .map(|p| p.x)
+                    .minmax()
+                    .into_option()
+                    .unwrap_or((500, 500));
+                let max_y = walls.map(|p| p.y).max().unwrap_or(0);
+                (
+                    Point { x: min_x - 1, y: 0 },
+                    Point {
+                        x: max_x + 1,
+                        y: max_y,
+                    },
+                )
+            }
+        };
+
+        let mut frame = Frame::from_sparse(grid, &min, &max, PALETTE, |cell| match cell {
+            None => 0,
+            Some(BlockerType::Wall) => 1,
+            Some(BlockerType::Sand) => 2,
+        });
+
+        if floor.is_some() {
+            (0..frame.width).for_each(|x| frame.set(x, frame.height - 1, 1));
+        }
+        frame
+    }
+}
 
 advent_of_code::main!(14);
 
+advent_of_code::tests! {
+    day: 14,
+    part_one: Some(24),
+    part_two: Some(93),
+}
+
 #[cfg(test)]
-mod tests {
-    use super::*;
+mod parse_tests {
+    use super::parse;
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(parse(&advent_of_code::template::read_file("examples", 14)));
-        assert_eq!(result, Some(24));
-    }
+    fn rejects_out_of_range_coordinates() {
+        let error = parse("498,4 -> 498,70000\n").err().unwrap();
+        assert_eq!((error.line, error.column), (1, 14));
+        assert_eq!(error.snippet, "70000");
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(parse(&advent_of_code::template::read_file("examples", 14)));
-        assert_eq!(result, Some(93));
+        let error = parse("498,4 -> 498,6\n-1,4 -> 2,4\n").err().unwrap();
+        assert_eq!((error.line, error.column), (2, 1));
     }
 }
@@ -1,3 +1,4 @@
+use advent_of_code::parse::{number, split_once, strip_prefix, ParseError};
 use advent_of_code::quadrant::Quadrant;
 use advent_of_code::range::{Range, RangeStack};
 use advent_of_code::Point;
@@ -35,26 +36,27 @@ fn find_unseen_points(pairs: &[Pair], quadrant: &Quadrant) -> Option<Point> {
         .next()
 }
 
-fn parse_point(s: &str) -> Option<Point> {
-    let (_, point_str) = s.split_once("at ")?;
-    let (x_str, y_str) = point_str.split_once(", ")?;
-    let (_, x) = x_str.split_once('=')?;
-    let (_, y) = y_str.split_once('=')?;
-    Some(Point {
-        x: x.parse().ok()?,
-        y: y.parse().ok()?,
+fn parse_point(input: &str, s: &str) -> Result<Point, ParseError> {
+    let (_, point_str) = split_once(input, s, "at ")?;
+    let (x_str, y_str) = split_once(input, point_str, ", ")?;
+    let x = strip_prefix(input, x_str, "x=")?;
+    let y = strip_prefix(input, y_str, "y=")?;
+    Ok(Point {
+        x: number(input, x)?,
+        y: number(input, y)?,
     })
 }
 
-fn parse(input: &str) -> Input {
+fn parse(input: &str) -> Result<Input, ParseError> {
     input
         .lines()
-        .filter_map(|l| {
-            let (sensor_str, beacon_str) = l.split_once(':')?;
-            let sensor = parse_point(sensor_str)?;
-            let beacon = parse_point(beacon_str)?;
+        .filter(|l| !l.is_empty())
+        .map(|l| {
+            let (sensor_str, beacon_str) = split_once(input, l, ":")?;
+            let sensor = parse_point(input, sensor_str)?;
+            let beacon = parse_point(input, beacon_str)?;
             let distance = sensor.manhattan_distance(&beacon);
-            Some(Pair {
+            Ok(Pair {
                 sensor,
                 beacon,
                 distance,
@@ -63,8 +65,8 @@ fn parse(input: &str) -> Input {
         .collect()
 }
 
-pub fn part_one(input: Input) -> Option<usize> {
-    let test_value = if cfg!(test) { 10 } else { 2000000 };
+pub fn part_one(input: Input, params: &Params) -> Option<usize> {
+    let test_value = params.row;
     let ranges = input
         .iter()
         .filter_map(|pair| {
@@ -97,35 +99,69 @@ pub fn part_one(input: Input) -> Option<usize> {
     )
 }
 
-pub fn part_two(input: Input) -> Option<isize> {
+pub fn part_two(input: Input, params: &Params) -> Option<isize> {
     let unseen_point = find_unseen_points(
         &input,
         &Quadrant {
             min: Point { x: 0, y: 0 },
             max: Point {
-                x: 4000000,
-                y: 4000000,
+                x: params.bound,
+                y: params.bound,
             },
         },
     )?;
     Some(unseen_point.x * 4000000 + unseen_point.y)
 }
 
-advent_of_code::main!(15);
+advent_of_code::main!(15, params: {
+    row: isize = 2_000_000,
+    bound: isize = 4_000_000,
+});
 
 #[cfg(test)]
-mod tests {
+mod reference {
     use super::*;
+    use rand::{rngs::StdRng, Rng};
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(parse(&advent_of_code::template::read_file("examples", 15)));
-        assert_eq!(result, Some(26));
+    /// A few sensors and their beacons around row 10, as in the example.
+    pub fn generate(rng: &mut StdRng) -> String {
+        let sensors = rng.gen_range(1..8);
+        let mut point = || (rng.gen_range(-5..=25), rng.gen_range(0..=20));
+
+        (0..sensors)
+            .map(|_| {
+                let ((sx, sy), (bx, by)) = (point(), point());
+                format!(
+                    "Sensor at x={}, y={}: closest beacon is at x={}, y={}\n",
+                    sx, sy, bx, by
+                )
+            })
+            .collect()
     }
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(parse(&advent_of_code::template::read_file("examples", 15)));
-        assert_eq!(result, Some(56000011));
+    /// Check every point of the row that some sensor could reach.
+    pub fn part_one(input: Input, params: &Params) -> Option<usize> {
+        let min_x = input.iter().map(|p| p.sensor.x - p.distance).min()?;
+        let max_x = input.iter().map(|p| p.sensor.x + p.distance).max()?;
+
+        Some(
+            (min_x..=max_x)
+                .map(|x| Point { x, y: params.row })
+                .filter(|point| {
+                    input
+                        .iter()
+                        .any(|p| p.sensor.manhattan_distance(point) <= p.distance)
+                        && !input.iter().any(|p| p.beacon == *point)
+                })
+                .count(),
+        )
     }
 }
+
+advent_of_code::reference_tests! {
+    generate: reference::generate,
+    params: { row: 10 },
+    part_one: reference::part_one,
+}
+
+advent_of_code::tests! { day: 15 }
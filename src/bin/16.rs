@@ -1,3 +1,4 @@
+use advent_of_code::parse::{number, split_once, strip_prefix, ParseError};
 use itertools::Itertools;
 use std::cmp::Reverse;
 use std::collections::HashMap;
@@ -134,28 +135,43 @@ fn floyd_warshall(rows: &[(&str, u8, Vec<&str>)]) -> Vec<Vec<u8>> {
     dist
 }
 
-fn parse_row(row: &str) -> (&str, u8, Vec<&str>) {
+fn parse_row<'a>(input: &str, row: &'a str) -> Result<(&'a str, u8, Vec<&'a str>), ParseError> {
     //Valve VR has flow rate=11; tunnels lead to valves LH, KV, BP
-    let (a, b) = row.split_once(" has flow rate=").unwrap();
-    let (b, c) = b.split_once(" to ").expect(b);
-    let b = match b.strip_suffix("; tunnels lead") {
-        Some(b) => b,
-        None => b.strip_suffix("; tunnel leads").expect(b),
-    };
-    let c = match c.strip_prefix("valves ") {
-        Some(c) => c,
-        None => c.strip_prefix("valve ").unwrap(),
-    };
-    (
-        a.strip_prefix("Valve ").unwrap(),
-        b.parse().unwrap(),
+    let (a, b) = split_once(input, row, " has flow rate=")?;
+    let (b, c) = split_once(input, b, " to ")?;
+    let b = b
+        .strip_suffix("; tunnels lead")
+        .or_else(|| b.strip_suffix("; tunnel leads"))
+        .ok_or_else(|| ParseError::at(input, b, "a flow rate and \"; tunnels lead\""))?;
+    let c = c
+        .strip_prefix("valves ")
+        .or_else(|| c.strip_prefix("valve "))
+        .ok_or_else(|| ParseError::at(input, c, "\"valves \""))?;
+    Ok((
+        strip_prefix(input, a, "Valve ")?,
+        number(input, b)?,
         c.split(", ").collect(),
-    )
+    ))
 }
 
-fn parse(input: &str) -> Input {
-    let rows = input.lines().map(parse_row).collect_vec();
-    let shortest_path_lengths_uncompressed = floyd_warshall(&rows);
+fn parse(input: &str) -> Result<Input, ParseError> {
+    let rows = input
+        .lines()
+        .map(|row| parse_row(input, row))
+        .collect::<Result<Vec<_>, ParseError>>()?;
+
+    let unknown_tunnel = rows
+        .iter()
+        .flat_map(|(_, _, tunnels)| tunnels)
+        .find(|&tunnel| !rows.iter().any(|(name, _, _)| name == tunnel));
+    if let Some(tunnel) = unknown_tunnel {
+        return Err(ParseError::at(input, tunnel, "the name of a valve"));
+    }
+
+    let shortest_path_lengths_uncompressed = {
+        let _s = advent_of_code::span!("floyd_warshall");
+        floyd_warshall(&rows)
+    };
 
     let interesting_valve_indices = rows
         .iter()
@@ -182,7 +198,7 @@ fn parse(input: &str) -> Input {
     let starting_node = interesting_valve_indices
         .iter()
         .position(|&i| rows[i].0 == "AA")
-        .expect("a valve called AA");
+        .ok_or_else(|| ParseError::after(input, input.trim_end(), "a valve called AA"))?;
 
     let sorted_flow_rate_indices = flow_rates
         .iter()
@@ -191,23 +207,25 @@ fn parse(input: &str) -> Input {
         .map(|(i, _)| i)
         .collect_vec();
 
-    (
+    Ok((
         flow_rates,
         shortest_path_lengths,
         sorted_flow_rate_indices,
         starting_node,
-    )
+    ))
 }
 
 pub fn part_one(
     (flow_rates, shortest_paths, sorted_flow_rate_indices, starting_idx): Input,
+    params: &Params,
 ) -> Option<u16> {
+    let _s = advent_of_code::span!("branch_and_bound");
     let mut best = 0;
     branch_and_bound(
         &flow_rates,
         &sorted_flow_rate_indices,
         &shortest_paths,
-        State::new(starting_idx as u8, 30),
+        State::new(starting_idx as u8, params.minutes),
         &mut [],
         &mut best,
         |bound, best| bound > best,
@@ -217,17 +235,23 @@ pub fn part_one(
 
 pub fn part_two(
     (flow_rates, shortest_paths, sorted_flow_rate_indices, starting_idx): Input,
+    params: &Params,
 ) -> Option<u16> {
     let mut best_per_visited = vec![0; u16::MAX as usize];
-    branch_and_bound(
-        &flow_rates,
-        &sorted_flow_rate_indices,
-        &shortest_paths,
-        State::new(starting_idx as u8, 26),
-        &mut best_per_visited,
-        &mut 0,
-        |bound, best| bound > best,
-    );
+    {
+        let _s = advent_of_code::span!("branch_and_bound");
+        branch_and_bound(
+            &flow_rates,
+            &sorted_flow_rate_indices,
+            &shortest_paths,
+            State::new(starting_idx as u8, params.minutes_with_elephant),
+            &mut best_per_visited,
+            &mut 0,
+            |bound, best| bound > best,
+        );
+    }
+
+    let _s = advent_of_code::span!("disjoint_pairs");
     let mut best = 0;
     let best_per_visited_filtered_sorted = best_per_visited
         .into_iter()
@@ -252,21 +276,9 @@ pub fn part_two(
     Some(best)
 }
 
-advent_of_code::main!(16);
+advent_of_code::main!(16, params: {
+    minutes: u8 = 30,
+    minutes_with_elephant: u8 = 26,
+});
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(parse(&advent_of_code::template::read_file("examples", 16)));
-        assert_eq!(result, Some(1651));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(parse(&advent_of_code::template::read_file("examples", 16)));
-        assert_eq!(result, Some(1707));
-    }
-}
+advent_of_code::tests! { day: 16 }
@@ -1,3 +1,4 @@
+use advent_of_code::ParseError;
 use std::collections::{hash_map::Entry, HashMap};
 
 #[derive(Debug, Clone, Copy)]
@@ -103,19 +104,24 @@ fn drop_rock(
     }
 }
 
-fn parse(input: &str) -> Input {
+fn parse(input: &str) -> Result<Input, ParseError> {
     input
-        .chars()
-        .filter_map(|c| match c {
-            '<' => Some(Wind::Left),
-            '>' => Some(Wind::Right),
-            _ => None,
+        .char_indices()
+        .filter(|(_, c)| !c.is_whitespace())
+        .map(|(i, c)| match c {
+            '<' => Ok(Wind::Left),
+            '>' => Ok(Wind::Right),
+            _ => Err(ParseError::at(
+                input,
+                &input[i..i + c.len_utf8()],
+                "\"<\" or \">\"",
+            )),
         })
         .collect()
 }
 
-pub fn part_one(input: Input) -> Option<usize> {
-    let num_rocks = 2022;
+pub fn part_one(input: Input, params: &Params) -> Option<usize> {
+    let num_rocks = params.rocks;
     let mut tower = Vec::with_capacity(num_rocks * 4);
 
     let mut wind_idx = 0;
@@ -126,8 +132,8 @@ pub fn part_one(input: Input) -> Option<usize> {
     Some(tower.len())
 }
 
-pub fn part_two(input: Input) -> Option<usize> {
-    let num_rocks = 1000000000000;
+pub fn part_two(input: Input, params: &Params) -> Option<usize> {
+    let num_rocks = params.many_rocks;
     let mut seen_states = HashMap::with_capacity(1024);
     let mut tower = Vec::with_capacity(1024);
 
@@ -167,21 +173,9 @@ pub fn part_two(input: Input) -> Option<usize> {
     Some(tower.len() + cycle_height)
 }
 
-advent_of_code::main!(17);
+advent_of_code::main!(17, params: {
+    rocks: usize = 2022,
+    many_rocks: usize = 1_000_000_000_000,
+});
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(parse(&advent_of_code::template::read_file("examples", 17)));
-        assert_eq!(result, Some(3068));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(parse(&advent_of_code::template::read_file("examples", 17)));
-        assert_eq!(result, Some(1514285714288));
-    }
-}
+advent_of_code::tests! { day: 17 }
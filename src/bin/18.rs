@@ -1,4 +1,4 @@
-use itertools::Itertools;
+use advent_of_code::parse::{next, number, ParseError};
 use std::collections::HashMap;
 use std::hash::Hash;
 
@@ -53,17 +53,23 @@ impl Cube {
 
 type Input = (Vec<Cube>, HashMap<u16, bool>, usize);
 
-fn parse(input: &str) -> Input {
+fn parse(input: &str) -> Result<Input, ParseError> {
     let cubes = input
         .lines()
-        .filter_map(|line| {
+        .filter(|line| !line.is_empty())
+        .map(|line| {
             let mut line_iter = line.split(",");
-            let x = line_iter.next()?.parse::<u16>().unwrap();
-            let y = line_iter.next()?.parse::<u16>().unwrap();
-            let z = line_iter.next()?.parse::<u16>().unwrap();
-            Some(Cube::from_pos(x, y, z))
+            let mut coordinate = || -> Result<u16, ParseError> {
+                let token = next(input, line, &mut line_iter, "a coordinate")?;
+                match number(input, token)? {
+                    // each coordinate is packed into 5 bits.
+                    n @ 0..=31 => Ok(n),
+                    _ => Err(ParseError::at(input, token, "a coordinate from 0 to 31")),
+                }
+            };
+            Ok(Cube::from_pos(coordinate()?, coordinate()?, coordinate()?))
         })
-        .collect_vec();
+        .collect::<Result<Vec<_>, ParseError>>()?;
     let mut cube_space_map = HashMap::new();
     let mut zero_count = 0;
     cubes.iter().for_each(|cube| {
@@ -73,7 +79,7 @@ fn parse(input: &str) -> Input {
             zero_count += 1;
         }
     });
-    (cubes, cube_space_map, zero_count)
+    Ok((cubes, cube_space_map, zero_count))
 }
 
 pub fn part_one(input: Input) -> Option<usize> {
@@ -120,19 +126,8 @@ pub fn part_two(input: Input) -> Option<usize> {
 
 advent_of_code::main!(18);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(parse(&advent_of_code::template::read_file("examples", 18)));
-        assert_eq!(result, Some(64));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(parse(&advent_of_code::template::read_file("examples", 18)));
-        assert_eq!(result, Some(58));
-    }
+advent_of_code::tests! {
+    day: 18,
+    part_one: Some(64),
+    part_two: Some(58),
 }
@@ -1,4 +1,5 @@
 use crate::Material::{Clay, Geode, Obsidian, Ore};
+use advent_of_code::parse::{number, ParseError};
 use itertools::Itertools;
 use rayon::prelude::*;
 use regex::Regex;
@@ -60,6 +61,7 @@ impl SearchState {
 type Input = Vec<Blueprint>;
 
 fn get_blueprint_score(blueprint: &Blueprint, time_remaining: u32) -> u32 {
+    let _s = advent_of_code::span!("blueprint");
     let state = SearchState {
         time_remaining,
         robots: [1, 0, 0, 0],
@@ -148,68 +150,79 @@ fn get_max_materials(blueprint: &Blueprint) -> [u32; 4] {
     maxes
 }
 
-fn parse(input: &str) -> Input {
-    input.lines().filter_map(|line| {
-        // Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 4 ore. Each obsidian robot costs 4 ore and 5 clay. Each geode robot costs 3 ore and 7 obsidian.
-        let regex = Regex::new(r"Blueprint (\d+): Each ore robot costs (\d+) ore. Each clay robot costs (\d+) ore. Each obsidian robot costs (\d+) ore and (\d+) clay. Each geode robot costs (\d+) ore and (\d+) obsidian.").unwrap();
-        let c = regex.captures(line)?;
-        let mut captures = c.iter().skip(1);
-
-        let id = captures.next()??.as_str().parse().unwrap();
-
-        let ore_robot_ore_cost = captures.next()??.as_str().parse().unwrap();
-        let clay_robot_ore_cost = captures.next()??.as_str().parse().unwrap();
-        let obsidian_robot_ore_cost = captures.next()??.as_str().parse().unwrap();
-        let obsidian_robot_clay_cost = captures.next()??.as_str().parse().unwrap();
-        let geode_robot_ore_cost = captures.next()??.as_str().parse().unwrap();
-        let geode_robot_obsidian_cost = captures.next()??.as_str().parse().unwrap();
-
-        let ore_robot: Vec<(u32, Material)> = vec![(ore_robot_ore_cost, Ore)];
-        let clay_robot: Vec<(u32, Material)> = vec![(clay_robot_ore_cost, Ore)];
-        let obsidian_robot: Vec<(u32, Material)> = vec![(obsidian_robot_ore_cost, Ore), (obsidian_robot_clay_cost, Clay)];
-        let geode_robot: Vec<(u32, Material)> = vec![(geode_robot_ore_cost, Ore), (geode_robot_obsidian_cost, Obsidian)];
-
-        Some(Blueprint {
-            id,
-            robot_recipes: [ore_robot, clay_robot, obsidian_robot, geode_robot],
+fn parse(input: &str) -> Result<Input, ParseError> {
+    // Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 4 ore. Each obsidian robot costs 4 ore and 5 clay. Each geode robot costs 3 ore and 7 obsidian.
+    let regex = Regex::new(r"Blueprint (\d+): Each ore robot costs (\d+) ore. Each clay robot costs (\d+) ore. Each obsidian robot costs (\d+) ore and (\d+) clay. Each geode robot costs (\d+) ore and (\d+) obsidian.").unwrap();
+
+    input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let c = regex
+                .captures(line)
+                .ok_or_else(|| ParseError::at(input, line, "a blueprint"))?;
+            let mut captures = c
+                .iter()
+                .skip(1)
+                .flatten()
+                .map(|m| number(input, m.as_str()));
+            let mut next = || {
+                captures
+                    .next()
+                    .unwrap_or_else(|| Err(ParseError::at(input, line, "a blueprint")))
+            };
+
+            let id = next()?;
+
+            let ore_robot_ore_cost = next()?;
+            let clay_robot_ore_cost = next()?;
+            let obsidian_robot_ore_cost = next()?;
+            let obsidian_robot_clay_cost = next()?;
+            let geode_robot_ore_cost = next()?;
+            let geode_robot_obsidian_cost = next()?;
+
+            let ore_robot: Vec<(u32, Material)> = vec![(ore_robot_ore_cost, Ore)];
+            let clay_robot: Vec<(u32, Material)> = vec![(clay_robot_ore_cost, Ore)];
+            let obsidian_robot: Vec<(u32, Material)> = vec![
+                (obsidian_robot_ore_cost, Ore),
+                (obsidian_robot_clay_cost, Clay),
+            ];
+            let geode_robot: Vec<(u32, Material)> = vec![
+                (geode_robot_ore_cost, Ore),
+                (geode_robot_obsidian_cost, Obsidian),
+            ];
+
+            Ok(Blueprint {
+                id,
+                robot_recipes: [ore_robot, clay_robot, obsidian_robot, geode_robot],
+            })
         })
-    }).collect_vec()
+        .collect()
 }
 
-pub fn part_one(input: Input) -> Option<u32> {
+pub fn part_one(input: Input, params: &Params) -> Option<u32> {
     Some(
         input
             .par_iter()
-            .map(|bp| (bp.id * get_blueprint_score(bp, 24)))
+            .map(|bp| (bp.id * get_blueprint_score(bp, params.minutes)))
             .sum::<u32>(),
     )
 }
 
-pub fn part_two(input: Input) -> Option<u32> {
+pub fn part_two(input: Input, params: &Params) -> Option<u32> {
     Some(
         input
             .par_iter()
-            .take(3)
-            .map(|bp| get_blueprint_score(bp, 32))
+            .take(params.blueprints)
+            .map(|bp| get_blueprint_score(bp, params.minutes_part_two))
             .product::<u32>(),
     )
 }
 
-advent_of_code::main!(19);
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(parse(&advent_of_code::template::read_file("examples", 19)));
-        assert_eq!(result, Some(33));
-    }
+advent_of_code::main!(19, params: {
+    minutes: u32 = 24,
+    minutes_part_two: u32 = 32,
+    blueprints: usize = 3,
+});
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(parse(&advent_of_code::template::read_file("examples", 19)));
-        assert_eq!(result, Some(3472));
-    }
-}
+advent_of_code::tests! { day: 19 }
@@ -1,13 +1,15 @@
+use advent_of_code::parse::{number, ParseError};
 use advent_of_code::treap::Treap;
 use itertools::Itertools;
 
 type Input = Vec<i64>;
 
-fn parse(input: &str) -> Input {
+fn parse(input: &str) -> Result<Input, ParseError> {
     input
         .lines()
-        .filter_map(|n| n.parse::<i64>().ok())
-        .collect_vec()
+        .filter(|n| !n.is_empty())
+        .map(|n| number::<i64>(input, n))
+        .collect()
 }
 
 fn decrypt(input: &[i64], multi: i64, k: usize) -> Option<i64> {
@@ -47,21 +49,71 @@ pub fn part_two(input: Input) -> Option<i64> {
     decrypt(&input, 811589153, 10)
 }
 
+/// Mixes by moving the numbers around in a `Vec`, which is linear per move.
+fn decrypt_vec(input: &[i64], multi: i64, k: usize) -> Option<i64> {
+    let len = input.len() as i64;
+    let mut order = (0..input.len()).collect_vec();
+
+    for _ in 0..k {
+        for (idx, n) in input.iter().enumerate() {
+            let pos = order.iter().position(|&i| i == idx)?;
+            order.remove(pos);
+            let new_pos = (pos as i64 + n * multi).rem_euclid(len - 1);
+            order.insert(new_pos as usize, idx);
+        }
+    }
+
+    let zero_pos = order.iter().position(|&i| input[i] == 0)?;
+    Some(
+        (1..=3)
+            .map(|k| input[order[(zero_pos + 1000 * k) % order.len()]] * multi)
+            .sum(),
+    )
+}
+
+pub fn part_one_vec(input: Input) -> Option<i64> {
+    decrypt_vec(&input, 1, 1)
+}
+
+pub fn part_two_vec(input: Input) -> Option<i64> {
+    decrypt_vec(&input, 811589153, 10)
+}
+
 advent_of_code::main!(20);
 
+advent_of_code::variants! {
+    part_one: [part_one_vec],
+    part_two: [part_two_vec],
+}
+
 #[cfg(test)]
-mod tests {
-    use super::*;
+mod reference {
+    use rand::{rngs::StdRng, Rng};
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(parse(&advent_of_code::template::read_file("examples", 20)));
-        assert_eq!(result, Some(3));
-    }
+    /// A mixing list with exactly one zero, and duplicates among the other numbers.
+    pub fn generate(rng: &mut StdRng) -> String {
+        let len = rng.gen_range(3..60);
+        let zero = rng.gen_range(0..len);
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(parse(&advent_of_code::template::read_file("examples", 20)));
-        assert_eq!(result, Some(1623178306));
+        (0..len)
+            .map(|i| match i {
+                i if i == zero => 0,
+                _ if rng.gen_bool(0.5) => rng.gen_range(1..100),
+                _ => -rng.gen_range(1..100),
+            })
+            .map(|n| n.to_string() + "\n")
+            .collect()
     }
 }
+
+advent_of_code::reference_tests! {
+    generate: reference::generate,
+    part_one: part_one_vec,
+    part_two: part_two_vec,
+}
+
+advent_of_code::tests! {
+    day: 20,
+    part_one: Some(3),
+    part_two: Some(1623178306),
+}
@@ -1,8 +1,8 @@
+use advent_of_code::parse::{next, number, split_once, ParseError};
 use std::collections::HashMap;
 
 type MonkeyId = u32;
 
-static ROOT_ID: MonkeyId = 0x746F6F72;
 static HUMN_ID: MonkeyId = 0x6E6D7568;
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -32,27 +32,46 @@ pub enum Monkey {
 }
 
 impl Monkey {
-    fn from_str(id: MonkeyId, known_values: &HashMap<MonkeyId, String>) -> Self {
-        let s = known_values.get(&id).unwrap();
+    /// The monkey called `name`, a slice of `input`, and the monkeys it waits for.
+    fn from_str(
+        input: &str,
+        name: &str,
+        known_values: &HashMap<MonkeyId, &str>,
+        depth: usize,
+    ) -> Result<Self, ParseError> {
+        let id = str_id_to_monkey_id(input, name)?;
+        let s = known_values
+            .get(&id)
+            .ok_or_else(|| ParseError::at(input, name, "the name of a monkey"))?;
+        if depth > known_values.len() {
+            return Err(ParseError::at(
+                input,
+                name,
+                "a monkey that does not wait for itself",
+            ));
+        }
+
         if s.chars().filter(|c| c.is_numeric()).count() > 0 {
-            let n = s.parse::<i64>().unwrap();
+            let n = number(input, s)?;
             if id == HUMN_ID {
-                Monkey::Human(n)
+                Ok(Monkey::Human(n))
             } else {
-                Monkey::Yell(n)
+                Ok(Monkey::Yell(n))
             }
         } else {
             let mut args = s.splitn(3, ' ');
-            let lhs = Monkey::from_str(str_id_to_monkey_id(args.next().unwrap()), known_values);
-            let op = match args.next().unwrap() {
+            let mut arg = || next(input, s, &mut args, "a job like \"aaaa + bbbb\"");
+            let lhs = Monkey::from_str(input, arg()?, known_values, depth + 1)?;
+            let op_str = arg()?;
+            let op = match op_str {
                 "+" => Op::Add,
                 "-" => Op::Sub,
                 "*" => Op::Mul,
                 "/" => Op::Div,
-                _ => panic!("\"{}\" is not a valid math operator", args.next().unwrap()),
+                _ => return Err(ParseError::at(input, op_str, "+, -, * or /")),
             };
-            let rhs = Monkey::from_str(str_id_to_monkey_id(args.next().unwrap()), known_values);
-            Monkey::Parent(Box::new((lhs, op, rhs)))
+            let rhs = Monkey::from_str(input, arg()?, known_values, depth + 1)?;
+            Ok(Monkey::Parent(Box::new((lhs, op, rhs))))
         }
     }
 
@@ -149,21 +168,24 @@ impl Monkey {
 
 type Input = Monkey;
 
-fn parse(input: &str) -> Input {
+fn parse(input: &str) -> Result<Input, ParseError> {
     let known_values = input
         .lines()
-        .filter_map(|line| {
-            let (id, yell_str) = line.split_once(": ")?;
-            Some((str_id_to_monkey_id(id), yell_str.to_string()))
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let (id, yell_str) = split_once(input, line, ": ")?;
+            Ok((str_id_to_monkey_id(input, id)?, yell_str))
         })
-        .collect();
+        .collect::<Result<_, ParseError>>()?;
 
-    Monkey::from_str(ROOT_ID, &known_values)
+    Monkey::from_str(input, "root", &known_values, 0)
 }
 
-fn str_id_to_monkey_id(id: &str) -> MonkeyId {
-    let id = id.as_bytes();
-    u32::from_le_bytes([id[0], id[1], id[2], id[3]])
+fn str_id_to_monkey_id(input: &str, id: &str) -> Result<MonkeyId, ParseError> {
+    match *id.as_bytes() {
+        [a, b, c, d] => Ok(u32::from_le_bytes([a, b, c, d])),
+        _ => Err(ParseError::at(input, id, "a name of four letters")),
+    }
 }
 
 pub fn part_one(input: Input) -> Option<i64> {
@@ -176,19 +198,8 @@ pub fn part_two(input: Input) -> Option<i64> {
 
 advent_of_code::main!(21);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(parse(&advent_of_code::template::read_file("examples", 21)));
-        assert_eq!(result, Some(152));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(parse(&advent_of_code::template::read_file("examples", 21)));
-        assert_eq!(result, Some(301));
-    }
+advent_of_code::tests! {
+    day: 21,
+    part_one: Some(152),
+    part_two: Some(301),
 }
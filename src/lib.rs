@@ -4,6 +4,8 @@ use std::{cmp, slice::Iter};
 
 pub mod template;
 
+pub use parse::ParseError;
+
 #[derive(Clone, Debug)]
 pub enum Direction {
     North,
@@ -247,6 +249,47 @@ impl<T> SimpleGrid<T> {
         }
     }
 
+    /// Like `from_str`, but fails on an empty input, on lines of different lengths, and on characters for which
+    /// the parser returns `None`, described by `expected`.
+    pub fn try_from_str(
+        input: &str,
+        parse: &mut dyn FnMut(char, usize, usize) -> Option<T>,
+        expected: &str,
+    ) -> Result<Self, ParseError> {
+        let lines: Vec<&str> = input.trim().lines().collect();
+        let width = match lines.first() {
+            Some(line) if !line.is_empty() => line.chars().count(),
+            _ => return Err(ParseError::at_offset(input, 0, 0, "a grid")),
+        };
+
+        let mut data = Vec::with_capacity(lines.len());
+        for (y, line) in lines.iter().enumerate() {
+            if line.chars().count() != width {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    format!("a row of {} cells", width),
+                ));
+            }
+
+            let row = line
+                .char_indices()
+                .enumerate()
+                .map(|(x, (i, c))| {
+                    parse(c, x, y)
+                        .ok_or_else(|| ParseError::at(input, &line[i..i + c.len_utf8()], expected))
+                })
+                .collect::<Result<Vec<T>, ParseError>>()?;
+            data.push(row);
+        }
+
+        Ok(SimpleGrid {
+            width,
+            height: data.len(),
+            data,
+        })
+    }
+
     /// Get a reference to the value for a certain point in the grid.
     /// NOTE: unchecked.
     pub fn get(&self, point: &Point) -> &T {
@@ -466,6 +509,90 @@ pub mod shortest_path {
 
         None
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::shortest_path;
+        use crate::template::properties::compare;
+        use crate::{Direction, Point, SimpleGrid};
+        use rand::Rng;
+
+        /// Cost of entering a cell, and whether it is a wall.
+        type Grid = SimpleGrid<(usize, bool)>;
+
+        /// Relax every edge until nothing changes (Bellman-Ford).
+        fn reference(grid: &Grid, start_points: &[Point], end_point: &Point) -> Option<usize> {
+            let mut dist = vec![usize::MAX; grid.width * grid.height];
+            start_points
+                .iter()
+                .for_each(|p| dist[grid.id_for_point(p)] = 0);
+
+            let mut changed = true;
+            while changed {
+                changed = false;
+                for point in grid.points() {
+                    let from = dist[grid.id_for_point(&point)];
+                    if from == usize::MAX {
+                        continue;
+                    }
+                    for direction in Direction::cardinal() {
+                        let next = point.get_neighbour(direction, 1);
+                        if !grid.is_inside(&next) || grid.get(&next).1 {
+                            continue;
+                        }
+                        let id = grid.id_for_point(&next);
+                        if from + grid.get(&next).0 < dist[id] {
+                            dist[id] = from + grid.get(&next).0;
+                            changed = true;
+                        }
+                    }
+                }
+            }
+
+            Some(dist[grid.id_for_point(end_point)]).filter(|d| *d != usize::MAX)
+        }
+
+        #[test]
+        fn finds_the_cheapest_path() {
+            compare(
+                |rng| {
+                    let (width, height) = (rng.gen_range(1..8), rng.gen_range(1..8));
+                    let data = (0..height)
+                        .map(|_| {
+                            (0..width)
+                                .map(|_| (rng.gen_range(1..10), rng.gen_bool(0.2)))
+                                .collect()
+                        })
+                        .collect();
+                    let mut point = || Point {
+                        x: rng.gen_range(0..width) as isize,
+                        y: rng.gen_range(0..height) as isize,
+                    };
+                    let starts = vec![point(), point()];
+                    let end = point();
+                    (
+                        Grid {
+                            width,
+                            height,
+                            data,
+                        },
+                        starts,
+                        end,
+                    )
+                },
+                |(grid, starts, end)| {
+                    shortest_path(
+                        grid,
+                        starts,
+                        end,
+                        |p| grid.get(p).0,
+                        |_, next| !grid.get(next).1,
+                    )
+                },
+                |(grid, starts, end)| reference(grid, starts, end),
+            );
+        }
+    }
 }
 
 pub mod range {
@@ -547,6 +674,42 @@ pub mod range {
             iterator.into_iter().cloned().collect()
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{Range, RangeStack};
+        use crate::template::properties::compare;
+        use rand::Rng;
+        use std::collections::HashSet;
+
+        #[test]
+        fn counts_covered_points() {
+            compare(
+                |rng| {
+                    (0..rng.gen_range(1..10))
+                        .map(|_| {
+                            let start = rng.gen_range(-30..30);
+                            (start, start + rng.gen_range(0..15))
+                        })
+                        .collect::<Vec<(isize, isize)>>()
+                },
+                |ranges| {
+                    let stack: RangeStack = ranges
+                        .iter()
+                        .map(|&(start, end)| Range::new(start, end))
+                        .collect();
+                    stack.count()
+                },
+                |ranges| {
+                    ranges
+                        .iter()
+                        .flat_map(|&(start, end)| start..=end)
+                        .collect::<HashSet<_>>()
+                        .len()
+                },
+            );
+        }
+    }
 }
 
 pub mod quadrant {
@@ -785,4 +948,563 @@ pub mod treap {
             Some((r.value, rank))
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{NodeKey, Treap};
+        use crate::template::properties::check;
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        /// An operation at a random position, taken modulo the current length.
+        #[derive(Debug)]
+        enum Op {
+            Insert(usize),
+            Remove(usize),
+        }
+
+        #[test]
+        fn behaves_like_a_vec() {
+            check(
+                |rng| {
+                    (0..rng.gen_range(1..200))
+                        .map(|_| match rng.gen_bool(0.6) {
+                            true => Op::Insert(rng.gen()),
+                            false => Op::Remove(rng.gen()),
+                        })
+                        .collect::<Vec<Op>>()
+                },
+                |ops| {
+                    let mut rng = StdRng::seed_from_u64(0);
+                    let mut treap = Treap::default();
+                    let mut model: Vec<(NodeKey, usize)> = vec![];
+
+                    for (value, op) in ops.iter().enumerate() {
+                        match *op {
+                            Op::Insert(pos) => {
+                                let rank = pos % (model.len() + 1);
+                                model.insert(rank, (treap.insert(value, rank, &mut rng), value));
+                            }
+                            Op::Remove(pos) if !model.is_empty() => {
+                                let rank = pos % model.len();
+                                let (node, value) = model.remove(rank);
+                                if treap.remove(node) != Some((value, rank)) {
+                                    return Err(format!("removing rank {} failed", rank));
+                                }
+                            }
+                            Op::Remove(_) => {}
+                        }
+                    }
+
+                    for (rank, (node, value)) in model.iter().enumerate() {
+                        if treap.rank(*node) != Some(rank)
+                            || treap.derank(rank) != *node
+                            || treap.get(*node) != Some(value)
+                        {
+                            return Err(format!("node at rank {} is out of place", rank));
+                        }
+                    }
+                    Ok(())
+                },
+            );
+        }
+    }
+}
+
+/// Recognise the block letters that some puzzles draw instead of printing an answer.
+/// Supports the usual 4×6 font and the larger 6×10 font; letters are separated by at least one blank column.
+pub mod ocr {
+    static FONT_6: [(char, &str); 18] = [
+        ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+        ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+        ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+        ('E', "####\n#...\n###.\n#...\n#...\n####"),
+        ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+        ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+        ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+        ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+        ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+        ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+        ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+        ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+        ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+        ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+        ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+        ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+        ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+        ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+    ];
+
+    static FONT_10: [(char, &str); 15] = [
+        (
+            'A',
+            "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+        ),
+        (
+            'B',
+            "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+        ),
+        (
+            'C',
+            ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+        ),
+        (
+            'E',
+            "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+        ),
+        (
+            'F',
+            "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+        ),
+        (
+            'G',
+            ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+        ),
+        (
+            'H',
+            "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+        ),
+        (
+            'J',
+            "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+        ),
+        (
+            'K',
+            "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+        ),
+        (
+            'L',
+            "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+        ),
+        (
+            'N',
+            "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+        ),
+        (
+            'P',
+            "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+        ),
+        (
+            'R',
+            "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+        ),
+        (
+            'X',
+            "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+        ),
+        (
+            'Z',
+            "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+        ),
+    ];
+
+    /// Whether a character of a drawing is a lit pixel.
+    pub fn is_lit(c: char) -> bool {
+        matches!(c, '#' | '▓' | '█')
+    }
+
+    fn to_grid(image: &str) -> Vec<Vec<bool>> {
+        image
+            .lines()
+            .map(|line| line.chars().map(is_lit).collect())
+            .collect()
+    }
+
+    /// Recognise the letters of a drawing such as `".##.\n#..#\n..."`. Lit pixels are `#`, `▓` or `█`.
+    /// Returns `None` if the drawing is not made of known letters.
+    pub fn read_str(image: &str) -> Option<String> {
+        read_grid(&to_grid(image))
+    }
+
+    /// Recognise the letters of a grid of pixels, indexed by row and column.
+    pub fn read_grid(grid: &[Vec<bool>]) -> Option<String> {
+        // drop blank rows around the letters.
+        let rows: Vec<&Vec<bool>> = grid.iter().skip_while(|row| !row.contains(&true)).collect();
+        let height = rows.iter().rposition(|row| row.contains(&true))? + 1;
+        let rows = &rows[..height];
+
+        let font: &[(char, &str)] = match height {
+            6 => &FONT_6,
+            10 => &FONT_10,
+            _ => return None,
+        };
+
+        let width = rows.iter().map(|row| row.len()).max()?;
+        let is_lit = |x: usize, y: usize| rows[y].get(x).copied().unwrap_or(false);
+        let is_blank_column = |x: usize| (0..height).all(|y| !is_lit(x, y));
+
+        let mut letters = String::new();
+        let mut x = 0;
+
+        while x < width {
+            if is_blank_column(x) {
+                x += 1;
+                continue;
+            }
+
+            let start = x;
+            while x < width && !is_blank_column(x) {
+                x += 1;
+            }
+
+            let glyph: Vec<String> = (0..height)
+                .map(|y| {
+                    (start..x)
+                        .map(|x| if is_lit(x, y) { '#' } else { '.' })
+                        .collect()
+                })
+                .collect();
+
+            let letter = font.iter().find(|(_, pattern)| {
+                let pattern: Vec<&str> = pattern.lines().collect();
+                trim_blank_columns(&pattern) == glyph
+            })?;
+            letters.push(letter.0);
+        }
+
+        Some(letters)
+    }
+
+    /// Font patterns may include blank columns on either side, which are not part of a glyph.
+    fn trim_blank_columns(pattern: &[&str]) -> Vec<String> {
+        let width = pattern.iter().map(|row| row.len()).max().unwrap_or(0);
+        let lit = |x: usize| {
+            pattern
+                .iter()
+                .any(|row| row.as_bytes().get(x) == Some(&b'#'))
+        };
+        let start = (0..width).find(|&x| lit(x)).unwrap_or(0);
+        let end = (0..width).rfind(|&x| lit(x)).map_or(0, |x| x + 1);
+
+        pattern
+            .iter()
+            .map(|row| row.get(start..end).unwrap_or_default().to_string())
+            .collect()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{read_grid, read_str, FONT_10, FONT_6};
+
+        /// Draw letters from a font side by side, separated by one blank column.
+        fn draw(font: &[(char, &str)], letters: &str, lit: char) -> String {
+            let glyphs: Vec<Vec<&str>> = letters
+                .chars()
+                .map(|c| {
+                    font.iter()
+                        .find(|(l, _)| *l == c)
+                        .unwrap()
+                        .1
+                        .lines()
+                        .collect()
+                })
+                .collect();
+
+            (0..glyphs[0].len())
+                .map(|y| {
+                    glyphs
+                        .iter()
+                        .map(|g| g[y].replace('#', &lit.to_string()).replace('.', "░"))
+                        .collect::<Vec<_>>()
+                        .join("░")
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+
+        #[test]
+        fn reads_small_font() {
+            assert_eq!(
+                read_str(&draw(&FONT_6, "EFGERURE", '▓')),
+                Some("EFGERURE".into())
+            );
+            let all: String = FONT_6.iter().map(|(c, _)| *c).collect();
+            assert_eq!(read_str(&draw(&FONT_6, &all, '#')), Some(all));
+        }
+
+        #[test]
+        fn reads_large_font() {
+            let all: String = FONT_10.iter().map(|(c, _)| *c).collect();
+            let image = format!("\n{}\n\n", draw(&FONT_10, &all, '#'));
+            assert_eq!(read_str(&image), Some(all));
+        }
+
+        #[test]
+        fn reads_grids_and_rejects_unknown_shapes() {
+            let grid: Vec<Vec<bool>> = draw(&FONT_6, "HI", '#')
+                .lines()
+                .map(|l| l.chars().map(|c| c == '#').collect())
+                .collect();
+            assert_eq!(read_grid(&grid), Some("HI".into()));
+            assert_eq!(read_str("##\n##\n##\n##\n##\n##"), None);
+            assert_eq!(read_str("#"), None);
+        }
+    }
+}
+
+/// Parse errors that point at the offending part of the input. A day's `parse` may return
+/// `Result<Input, ParseError>` instead of `Input`, in which case `main!` prints a failure as a diagnostic instead of
+/// panicking. The helpers take the whole input as their first argument, to locate the slice they fail on in it.
+pub mod parse {
+    use std::{error, fmt, str::FromStr};
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct ParseError {
+        /// Line of the input, starting at 1.
+        pub line: usize,
+        /// Column in characters, starting at 1.
+        pub column: usize,
+        /// The offending text, empty at the end of a line.
+        pub snippet: String,
+        /// What the parser expected instead, e.g. `a number`.
+        pub expected: String,
+        /// The whole line, shown in the diagnostic.
+        pub source_line: String,
+    }
+
+    fn floor_char_boundary(s: &str, index: usize) -> usize {
+        let mut index = index.min(s.len());
+        while !s.is_char_boundary(index) {
+            index -= 1;
+        }
+        index
+    }
+
+    /// The byte offset of `token` in `input`. A token that is not a slice of the input is searched for in it.
+    fn locate(input: &str, token: &str) -> usize {
+        let start = input.as_ptr() as usize;
+        let pointer = token.as_ptr() as usize;
+
+        if pointer >= start && pointer + token.len() <= start + input.len() {
+            pointer - start
+        } else {
+            input.find(token).unwrap_or(input.len())
+        }
+    }
+
+    impl ParseError {
+        /// An error at `token`, a slice of `input`.
+        pub fn at(input: &str, token: &str, expected: impl Into<String>) -> Self {
+            ParseError::at_offset(input, locate(input, token), token.len(), expected)
+        }
+
+        /// An error right after `token`, a slice of `input`, e.g. for a missing field at the end of a line.
+        pub fn after(input: &str, token: &str, expected: impl Into<String>) -> Self {
+            ParseError::at_offset(input, locate(input, token) + token.len(), 0, expected)
+        }
+
+        /// An error at the byte `offset` of `input`, over `len` bytes. The snippet ends with the line.
+        pub fn at_offset(
+            input: &str,
+            offset: usize,
+            len: usize,
+            expected: impl Into<String>,
+        ) -> Self {
+            let offset = floor_char_boundary(input, offset);
+            let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+            let line_end = input[offset..]
+                .find('\n')
+                .map_or(input.len(), |i| offset + i);
+            let end = floor_char_boundary(input, (offset + len).min(line_end)).max(offset);
+
+            ParseError {
+                line: input[..offset].matches('\n').count() + 1,
+                column: input[line_start..offset].chars().count() + 1,
+                snippet: input[offset..end].trim_end_matches('\r').to_string(),
+                expected: expected.into(),
+                source_line: input[line_start..line_end]
+                    .trim_end_matches('\r')
+                    .to_string(),
+            }
+        }
+    }
+
+    /// An `expected … found …` line, followed by the line of the input with the snippet underlined.
+    impl fmt::Display for ParseError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let found = match self.snippet.is_empty() {
+                true => "the end of the line".to_string(),
+                false => format!("{:?}", self.snippet),
+            };
+            let number = self.line.to_string();
+            let gutter = " ".repeat(number.len());
+
+            writeln!(
+                f,
+                "expected {}, found {} at line {}, column {}",
+                self.expected, found, self.line, self.column
+            )?;
+            writeln!(f, "{} |", gutter)?;
+            writeln!(f, "{} | {}", number, self.source_line)?;
+            write!(
+                f,
+                "{} | {}{}",
+                gutter,
+                " ".repeat(self.column - 1),
+                "^".repeat(self.snippet.chars().count().max(1))
+            )
+        }
+    }
+
+    impl error::Error for ParseError {}
+
+    /// Parse `token`, a slice of `input`, as a number.
+    pub fn number<T: FromStr>(input: &str, token: &str) -> Result<T, ParseError> {
+        token
+            .parse()
+            .map_err(|_| ParseError::at(input, token, "a number"))
+    }
+
+    /// Split `s`, a slice of `input`, at the first `delimiter`.
+    pub fn split_once<'a>(
+        input: &str,
+        s: &'a str,
+        delimiter: &str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        s.split_once(delimiter)
+            .ok_or_else(|| ParseError::at(input, s, format!("{:?}", delimiter)))
+    }
+
+    /// Remove `prefix` from `s`, a slice of `input`.
+    pub fn strip_prefix<'a>(input: &str, s: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+        s.strip_prefix(prefix)
+            .ok_or_else(|| ParseError::at(input, s, format!("{:?}", prefix)))
+    }
+
+    /// The next field of a line, split from `line`, a slice of `input`.
+    pub fn next<'a>(
+        input: &str,
+        line: &str,
+        fields: &mut impl Iterator<Item = &'a str>,
+        expected: &str,
+    ) -> Result<&'a str, ParseError> {
+        fields
+            .next()
+            .ok_or_else(|| ParseError::after(input, line, expected))
+    }
+
+    /// Marks a `parse` that returns its input, see `IntoParsed`.
+    pub struct Infallible;
+    /// Marks a `parse` that returns `Result<Input, ParseError>`.
+    pub struct Fallible;
+
+    /// What a day's `parse` returns, either the input or a `Result`. The marker `M` tells the two apart, so that
+    /// `main!` accepts both wherever the type of the input is known.
+    pub trait IntoParsed<T, M> {
+        fn into_parsed(self) -> Result<T, ParseError>;
+    }
+
+    impl<T> IntoParsed<T, Infallible> for T {
+        fn into_parsed(self) -> Result<T, ParseError> {
+            Ok(self)
+        }
+    }
+
+    impl<T> IntoParsed<T, Fallible> for Result<T, ParseError> {
+        fn into_parsed(self) -> Result<T, ParseError> {
+            self
+        }
+    }
+
+    /// The parsed input, panicking with the diagnostic if it failed. Used by the tests of days.
+    pub fn unwrap<T, M>(parsed: impl IntoParsed<T, M>) -> T {
+        parsed.into_parsed().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{number, split_once, strip_prefix, ParseError};
+        use crate::SimpleGrid;
+
+        #[test]
+        fn locates_errors() {
+            let input = "1,2\n3,x4\n5";
+            let token = &input[6..8];
+            let error = number::<u32>(input, token).unwrap_err();
+
+            assert_eq!((error.line, error.column), (2, 3));
+            assert_eq!(error.snippet, "x4");
+            assert_eq!(
+                error.to_string(),
+                "expected a number, found \"x4\" at line 2, column 3\n  |\n2 | 3,x4\n  |   ^^"
+            );
+
+            let line = &input[9..];
+            let error = split_once(input, line, ",").unwrap_err();
+            assert_eq!(
+                (error.line, error.column, error.expected.as_str()),
+                (3, 1, "\",\"")
+            );
+
+            let error = ParseError::after(input, &input[..3], "a third field");
+            assert_eq!(
+                (error.line, error.column, error.snippet.as_str()),
+                (1, 4, "")
+            );
+            assert!(error.to_string().contains("found the end of the line"));
+
+            assert_eq!(strip_prefix(input, &input[4..], "3,"), Ok("x4\n5"));
+        }
+
+        #[test]
+        fn reads_grids() {
+            let mut digit = |c: char, _, _| c.to_digit(10);
+            let grid = SimpleGrid::try_from_str("12\n34\n", &mut digit, "a digit").unwrap();
+            assert_eq!(grid.data, vec![vec![1, 2], vec![3, 4]]);
+
+            let error = SimpleGrid::try_from_str("12\n3x\n", &mut digit, "a digit").unwrap_err();
+            assert_eq!((error.line, error.column), (2, 2));
+            assert_eq!(error.expected, "a digit");
+
+            let error = SimpleGrid::try_from_str("12\n3\n", &mut digit, "a digit").unwrap_err();
+            assert_eq!(
+                (error.snippet.as_str(), error.expected.as_str()),
+                ("3", "a row of 2 cells")
+            );
+            assert!(SimpleGrid::try_from_str("\n", &mut digit, "a digit").is_err());
+        }
+
+        #[test]
+        fn finds_copied_tokens() {
+            let error = ParseError::at("a\nb c\n", "c", "d");
+            assert_eq!((error.line, error.column), (2, 3));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::merge_ranges;
+    use crate::template::properties::check;
+    use rand::Rng;
+    use std::{collections::BTreeSet, ops::Range};
+
+    #[test]
+    fn merged_ranges_cover_the_same_points() {
+        check(
+            |rng| {
+                let mut ranges: Vec<Range<isize>> = (0..rng.gen_range(0..10))
+                    .map(|_| {
+                        let start = rng.gen_range(-20..20);
+                        start..start + rng.gen_range(0..10)
+                    })
+                    .collect();
+                ranges.sort_by_key(|r| r.start);
+                ranges
+            },
+            |ranges| {
+                let merged: Vec<Range<isize>> = merge_ranges(ranges.clone()).collect();
+                let points = |ranges: &[Range<isize>]| -> BTreeSet<isize> {
+                    ranges.iter().flat_map(|r| r.clone()).collect()
+                };
+
+                if points(ranges) != points(&merged) {
+                    Err(format!("merged into {:?}", merged))
+                } else if merged.windows(2).any(|w| w[0].end >= w[1].start) {
+                    Err(format!("{:?} overlap or touch", merged))
+                } else {
+                    Ok(())
+                }
+            },
+        );
+    }
 }
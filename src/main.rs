@@ -1,10 +1,15 @@
 use advent_of_code::template::commands::{
-    all::all_handler, download::download_handler, read::read_handler, scaffold::scaffold_handler,
-    solve::solve_handler,
+    all::all_handler, bench_compare::bench_compare_handler, download::download_handler,
+    extract::extract_handler, read::read_handler, scaffold::scaffold_handler, solve::solve_handler,
+    verify::verify_handler, watch::watch_handler,
 };
 use args::{parse_args, AppArgs};
 
 mod args {
+    use advent_of_code::template::{
+        commands::solve,
+        selection::{parse_days, Selection},
+    };
     use std::process;
 
     pub enum AppArgs {
@@ -17,15 +22,33 @@ mod args {
         Scaffold {
             day: u8,
         },
+        Extract {
+            day: u8,
+            block: Option<usize>,
+            overwrite: bool,
+        },
         Solve {
+            day: u8,
+            options: solve::Options,
+        },
+        Watch {
             day: u8,
             release: bool,
-            time: bool,
-            submit: Option<u8>,
         },
         All {
             release: bool,
             time: bool,
+            timeout: Option<f64>,
+            selection: Selection,
+        },
+        Verify {
+            release: bool,
+            timeout: Option<f64>,
+        },
+        BenchCompare {
+            baseline: Option<String>,
+            run: Option<String>,
+            threshold: f64,
         },
     }
 
@@ -36,6 +59,24 @@ mod args {
             Some("all") => AppArgs::All {
                 release: args.contains("--release"),
                 time: args.contains("--time"),
+                timeout: args.opt_value_from_str("--timeout")?,
+                selection: Selection {
+                    days: args.opt_value_from_fn("--days", parse_days)?,
+                    skip: args
+                        .opt_value_from_fn("--skip", parse_days)?
+                        .unwrap_or_default(),
+                    only_failing: args.contains("--only-failing"),
+                    slow_threshold: args.opt_value_from_str("--slow-threshold")?,
+                },
+            },
+            Some("verify") => AppArgs::Verify {
+                release: args.contains("--release"),
+                timeout: args.opt_value_from_str("--timeout")?,
+            },
+            Some("bench-compare") => AppArgs::BenchCompare {
+                baseline: args.opt_value_from_str("--baseline")?,
+                run: args.opt_value_from_str("--run")?,
+                threshold: args.opt_value_from_str("--threshold")?.unwrap_or(10.0),
             },
             Some("download") => AppArgs::Download {
                 day: args.free_from_str()?,
@@ -46,11 +87,30 @@ mod args {
             Some("scaffold") => AppArgs::Scaffold {
                 day: args.free_from_str()?,
             },
+            Some("extract") => AppArgs::Extract {
+                day: args.free_from_str()?,
+                block: args.opt_value_from_str("--block")?,
+                overwrite: args.contains("--overwrite"),
+            },
             Some("solve") => AppArgs::Solve {
+                day: args.free_from_str()?,
+                options: solve::Options {
+                    release: args.contains("--release"),
+                    submit_part: args.opt_value_from_str("--submit")?,
+                    time: args.contains("--time"),
+                    timeout: args.opt_value_from_str("--timeout")?,
+                    alloc: args.contains("--alloc"),
+                    spans: args.contains("--spans"),
+                    variants: args.contains("--variants"),
+                    viz: args.opt_value_from_str("--viz")?,
+                    force: args.contains("--force"),
+                    params: args.values_from_str("--param")?,
+                    input: args.opt_value_from_str("--input")?,
+                },
+            },
+            Some("watch") => AppArgs::Watch {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
             },
             Some(x) => {
                 eprintln!("Unknown command: {}", x);
@@ -78,16 +138,28 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArgs::All { release, time } => all_handler(release, time),
+            AppArgs::All {
+                release,
+                time,
+                timeout,
+                selection,
+            } => all_handler(release, time, timeout, selection),
+            AppArgs::Verify { release, timeout } => verify_handler(release, timeout),
+            AppArgs::BenchCompare {
+                baseline,
+                run,
+                threshold,
+            } => bench_compare_handler(baseline, run, threshold),
             AppArgs::Download { day } => download_handler(day),
             AppArgs::Read { day } => read_handler(day),
             AppArgs::Scaffold { day } => scaffold_handler(day),
-            AppArgs::Solve {
+            AppArgs::Extract {
                 day,
-                release,
-                time,
-                submit,
-            } => solve_handler(day, release, time, submit),
+                block,
+                overwrite,
+            } => extract_handler(day, block, overwrite),
+            AppArgs::Solve { day, options } => solve_handler(day, options),
+            AppArgs::Watch { day, release } => watch_handler(day, release),
         },
     };
 }
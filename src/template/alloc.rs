@@ -0,0 +1,129 @@
+/// Allocation profiling. With the `alloc-profile` feature enabled, `main!` installs `CountingAllocator` as the
+/// global allocator and the runner reports allocations for the parser and each part.
+/// The counters are process-wide: allocations on every thread are charged to the part being measured, including those
+/// of rayon workers and of a part that timed out and keeps running in the background.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// Wraps the system allocator and counts every allocation.
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn record_allocation(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(size, Ordering::Relaxed);
+        let live = LIVE_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK_LIVE_BYTES.fetch_max(live, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::record_allocation(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::record_allocation(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+            Self::record_allocation(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Allocations made while a `Tracker` was running.
+/// `peak_bytes` is the highest number of live bytes above the level at the start.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub allocations: usize,
+    pub bytes: usize,
+    pub peak_bytes: usize,
+}
+
+pub struct Tracker {
+    allocations: usize,
+    bytes: usize,
+    live_bytes: usize,
+}
+
+impl Tracker {
+    pub fn start() -> Self {
+        let live_bytes = LIVE_BYTES.load(Ordering::Relaxed);
+        PEAK_LIVE_BYTES.store(live_bytes, Ordering::Relaxed);
+
+        Tracker {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            bytes: ALLOCATED_BYTES.load(Ordering::Relaxed),
+            live_bytes,
+        }
+    }
+
+    /// Returns `None` unless the counting allocator is installed.
+    pub fn finish(self) -> Option<Stats> {
+        if !cfg!(feature = "alloc-profile") {
+            return None;
+        }
+
+        Some(self.stats())
+    }
+
+    fn stats(&self) -> Stats {
+        Stats {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed) - self.allocations,
+            bytes: ALLOCATED_BYTES.load(Ordering::Relaxed) - self.bytes,
+            peak_bytes: PEAK_LIVE_BYTES
+                .load(Ordering::Relaxed)
+                .saturating_sub(self.live_bytes),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CountingAllocator, Stats, Tracker};
+    use std::alloc::{GlobalAlloc, Layout};
+
+    #[test]
+    fn counts_allocations() {
+        let tracker = Tracker::start();
+        let layout = Layout::from_size_align(1024, 8).unwrap();
+
+        unsafe {
+            let ptr = CountingAllocator.alloc(layout);
+            let ptr = CountingAllocator.realloc(ptr, layout, 2048);
+            CountingAllocator.dealloc(ptr, Layout::from_size_align(2048, 8).unwrap());
+        }
+
+        assert_eq!(
+            tracker.stats(),
+            Stats {
+                allocations: 2,
+                bytes: 3072,
+                peak_bytes: 2048,
+            }
+        );
+    }
+}
@@ -0,0 +1,149 @@
+/// Known-answer store. Accepted answers live in `data/answers/DD.toml`:
+///
+/// ```toml
+/// part_one = 72511
+/// part_two = "EFGERURE"
+/// ```
+use crate::template::{input, toml};
+use std::{fmt::Display, fs, io, path::PathBuf};
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(toml::Error),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "malformed answers file: {}", e),
+            Error::IO(e) => write!(f, "could not access answers file: {}", e),
+        }
+    }
+}
+
+/// Result of comparing a part's output with the stored answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Check {
+    Correct,
+    Incorrect,
+    Unknown,
+}
+
+impl Check {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Check::Correct => "✓",
+            Check::Incorrect => "✗",
+            Check::Unknown => "?",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl Answers {
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_one.as_deref(),
+            2 => self.part_two.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn check(&self, part: u8, result: &str) -> Check {
+        match self.get(part) {
+            Some(expected) if expected == result => Check::Correct,
+            Some(_) => Check::Incorrect,
+            None => Check::Unknown,
+        }
+    }
+
+    pub(crate) fn from_str(s: &str) -> Result<Self, Error> {
+        let doc = toml::parse(s).map_err(Error::Parser)?;
+        Ok(doc.root().map(Self::from_table).unwrap_or_default())
+    }
+
+    pub(crate) fn from_table(table: &toml::Table) -> Self {
+        Answers {
+            part_one: table.get("part_one").map(String::from),
+            part_two: table.get("part_two").map(String::from),
+        }
+    }
+
+    pub(crate) fn to_toml(&self) -> String {
+        [("part_one", &self.part_one), ("part_two", &self.part_two)]
+            .iter()
+            .filter_map(|(key, value)| {
+                let value = value.as_ref()?;
+                Some(format!("{} = {}\n", key, toml::format_value(value)))
+            })
+            .collect()
+    }
+}
+
+pub fn get_answers_path(day: u8) -> PathBuf {
+    input::get_day_path("answers", day, "toml")
+}
+
+/// Read the stored answers for a day. A missing file means no answers are known yet.
+pub fn read(day: u8) -> Result<Answers, Error> {
+    match fs::read_to_string(get_answers_path(day)) {
+        Ok(s) => Answers::from_str(&s),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Store the accepted answer for one part, keeping the other part's answer.
+pub fn write(day: u8, part: u8, answer: &str) -> Result<(), Error> {
+    let mut answers = read(day)?;
+    match part {
+        1 => answers.part_one = Some(answer.to_string()),
+        2 => answers.part_two = Some(answer.to_string()),
+        _ => return Ok(()),
+    }
+
+    let path = get_answers_path(day);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, answers.to_toml())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Answers, Check};
+
+    #[test]
+    fn checks_against_stored_answers() {
+        let answers = Answers::from_str("part_one = 24000\n").unwrap();
+        assert_eq!(answers.check(1, "24000"), Check::Correct);
+        assert_eq!(answers.check(1, "24001"), Check::Incorrect);
+        assert_eq!(answers.check(2, "45000"), Check::Unknown);
+    }
+
+    #[test]
+    fn round_trips_through_toml() {
+        let answers = Answers {
+            part_one: Some("24000".into()),
+            part_two: Some("TDCHVHJTG".into()),
+        };
+        assert_eq!(
+            answers.to_toml(),
+            "part_one = 24000\npart_two = \"TDCHVHJTG\"\n"
+        );
+        assert_eq!(Answers::from_str(&answers.to_toml()).unwrap(), answers);
+    }
+}
@@ -0,0 +1,441 @@
+/// Minimal Advent of Code HTTP client used by `download`, `read` and `solve --submit`.
+///
+/// Configured from the environment:
+///  - `AOC_SESSION`: the session cookie. Falls back to the file in `AOC_SESSION_FILE` or `~/.adventofcode.session`.
+///  - `AOC_BASE_URL`: defaults to `https://adventofcode.com`.
+///  - `AOC_YEAR`: defaults to 2022.
+use std::{env, fmt::Display, fs, io, path::PathBuf, time::Duration};
+
+use crate::template::markdown;
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const DEFAULT_YEAR: u16 = 2022;
+const USER_AGENT: &str = "github.com/foxwhite25/advent-of-code-2022 (built-in client)";
+
+#[derive(Debug)]
+pub enum Error {
+    MissingSession,
+    Status(u16, String),
+    Transport(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<ureq::Error> for Error {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(code, response) => {
+                Error::Status(code, response.into_string().unwrap_or_default())
+            }
+            ureq::Error::Transport(e) => Error::Transport(e.to_string()),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MissingSession => write!(
+                f,
+                "no session cookie found. Set AOC_SESSION or write it to \"~/.adventofcode.session\"."
+            ),
+            Error::Status(404, _) => write!(f, "not found, is the puzzle unlocked yet?"),
+            Error::Status(400 | 500, _) => {
+                write!(f, "request was rejected, is the session cookie still valid?")
+            }
+            Error::Status(code, _) => write!(f, "server responded with status {}.", code),
+            Error::Transport(e) => write!(f, "request failed: {}", e),
+            Error::IO(e) => write!(f, "could not read session file: {}", e),
+        }
+    }
+}
+
+/// Parsed response to an answer submission.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    /// Answered too recently, the server asks to wait this many seconds.
+    Wait(u64),
+    AlreadySolved,
+    /// A response we don't recognize, with the text of the message.
+    Unknown(String),
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "✓ That's the right answer!"),
+            Outcome::TooHigh => write!(f, "✗ That's not the right answer, it is too high."),
+            Outcome::TooLow => write!(f, "✗ That's not the right answer, it is too low."),
+            Outcome::Incorrect => write!(f, "✗ That's not the right answer."),
+            Outcome::Wait(seconds) => write!(
+                f,
+                "You gave an answer too recently, wait {}s before trying again.",
+                seconds
+            ),
+            Outcome::AlreadySolved => write!(f, "This part is already solved."),
+            Outcome::Unknown(message) => write!(f, "Unexpected response: {}", message),
+        }
+    }
+}
+
+/// Outcome of a submission and how many seconds the server wants us to wait before the next one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reply {
+    pub outcome: Outcome,
+    pub cooldown: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Config {
+    pub session: String,
+    pub base_url: String,
+    pub year: u16,
+}
+
+impl Config {
+    pub fn from_env() -> Result<Self, Error> {
+        let session = match env::var("AOC_SESSION") {
+            Ok(session) => session,
+            Err(_) => read_session_file()?,
+        };
+
+        let session = session.trim().trim_start_matches("session=").to_string();
+        if session.is_empty() {
+            return Err(Error::MissingSession);
+        }
+
+        Ok(Config {
+            session,
+            base_url: env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into()),
+            year: get_year(),
+        })
+    }
+}
+
+/// The event year, from `AOC_YEAR`.
+pub fn get_year() -> u16 {
+    env::var("AOC_YEAR")
+        .ok()
+        .and_then(|year| year.parse().ok())
+        .unwrap_or(DEFAULT_YEAR)
+}
+
+fn read_session_file() -> Result<String, Error> {
+    let path = match env::var("AOC_SESSION_FILE") {
+        Ok(path) => PathBuf::from(path),
+        Err(_) => match env::var("HOME") {
+            Ok(home) => PathBuf::from(home).join(".adventofcode.session"),
+            Err(_) => return Err(Error::MissingSession),
+        },
+    };
+
+    match fs::read_to_string(path) {
+        Ok(session) => Ok(session),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(Error::MissingSession),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub struct Client {
+    config: Config,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(config: Config) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+        Client { config, agent }
+    }
+
+    pub fn from_env() -> Result<Self, Error> {
+        Ok(Self::new(Config::from_env()?))
+    }
+
+    fn day_url(&self, day: u8) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.config.base_url.trim_end_matches('/'),
+            self.config.year,
+            day
+        )
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.config.session)
+    }
+
+    fn get(&self, url: &str) -> Result<String, Error> {
+        let response = self.agent.get(url).set("Cookie", &self.cookie()).call()?;
+        response.into_string().map_err(Error::from)
+    }
+
+    pub fn get_input(&self, day: u8) -> Result<String, Error> {
+        self.get(&format!("{}/input", self.day_url(day)))
+    }
+
+    /// Fetch the puzzle description, converted to markdown.
+    pub fn get_puzzle(&self, day: u8) -> Result<String, Error> {
+        let html = self.get(&self.day_url(day))?;
+        Ok(markdown::from_html(&html))
+    }
+
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> Result<Reply, Error> {
+        let response = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(day)))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+
+        let html = response.into_string()?;
+        Ok(Reply {
+            outcome: parse_outcome(&html),
+            cooldown: parse_cooldown(&html),
+        })
+    }
+}
+
+/// Classify the response page of an answer submission.
+pub fn parse_outcome(html: &str) -> Outcome {
+    let message = markdown::from_html(article(html).unwrap_or(html));
+    let message = message.trim();
+
+    if message.contains("That's the right answer") {
+        Outcome::Correct
+    } else if message.contains("answer too recently") {
+        Outcome::Wait(parse_wait(message).unwrap_or(60))
+    } else if message.contains("your answer is too high") {
+        Outcome::TooHigh
+    } else if message.contains("your answer is too low") {
+        Outcome::TooLow
+    } else if message.contains("That's not the right answer") {
+        Outcome::Incorrect
+    } else if message.contains("Did you already complete it") {
+        Outcome::AlreadySolved
+    } else {
+        Outcome::Unknown(message.to_string())
+    }
+}
+
+/// Parse the wait time out of a submission response, e.g. "please wait 5 minutes before trying again".
+pub fn parse_cooldown(html: &str) -> Option<u64> {
+    let message = markdown::from_html(article(html).unwrap_or(html));
+
+    if let Some(seconds) = parse_wait(&message) {
+        return Some(seconds);
+    }
+
+    let message = message.to_lowercase();
+    let start = message.find("please wait ")? + "please wait ".len();
+    let (amount, rest) = message[start..].split_once(' ')?;
+    let amount = match amount {
+        "one" => 1,
+        amount => amount.parse().ok()?,
+    };
+
+    if rest.starts_with("minute") {
+        Some(amount * 60)
+    } else if rest.starts_with("second") {
+        Some(amount)
+    } else {
+        None
+    }
+}
+
+fn article(html: &str) -> Option<&str> {
+    let start = html.find("<article")?;
+    let end = html[start..].find("</article>")? + start;
+    Some(&html[start..end + "</article>".len()])
+}
+
+/// Parse "You have 1m 12s left to wait." into seconds.
+fn parse_wait(message: &str) -> Option<u64> {
+    let start = message.find("You have ")? + "You have ".len();
+    let end = message[start..].find(" left to wait")? + start;
+
+    message[start..end]
+        .split_whitespace()
+        .map(|part| {
+            let (value, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let value: u64 = value.parse().ok()?;
+            match unit {
+                "h" => Some(value * 3600),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_cooldown, parse_outcome, Client, Config, Outcome};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// Serve a single request on a random local port. The handle yields the raw request.
+    fn mock_server(status: &'static str, body: &'static str) -> (Config, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let config = Config {
+            session: "abc".into(),
+            base_url: format!("http://{}", listener.local_addr().unwrap()),
+            year: 2022,
+        };
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+                request.push_str(&line);
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+            }
+
+            let mut body_bytes = vec![0; content_length];
+            reader.read_exact(&mut body_bytes).unwrap();
+            request.push_str(&String::from_utf8(body_bytes).unwrap());
+
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            request
+        });
+
+        (config, handle)
+    }
+
+    #[test]
+    fn fetches_input() {
+        let (config, server) = mock_server("200 OK", "1000\n2000\n");
+        let input = Client::new(config).get_input(1).unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(input, "1000\n2000\n");
+        assert!(request.starts_with("GET /2022/day/1/input "));
+        assert!(request.contains("session=abc"));
+    }
+
+    #[test]
+    fn fetches_puzzle_as_markdown() {
+        let (config, server) = mock_server(
+            "200 OK",
+            "<html><main><article><h2>--- Day 1: Calorie Counting ---</h2><p>Hi <em>there</em>.</p></article></main></html>",
+        );
+        let puzzle = Client::new(config).get_puzzle(1).unwrap();
+        server.join().unwrap();
+
+        assert_eq!(
+            puzzle,
+            "\\--- Day 1: Calorie Counting ---\n----------\n\nHi *there*.\n"
+        );
+    }
+
+    #[test]
+    fn submits_answer() {
+        let (config, server) = mock_server(
+            "200 OK",
+            "<main><article><p>That's the right answer!  You are <em>one gold star</em> closer.</p></article></main>",
+        );
+        let reply = Client::new(config).submit(1, 2, "45000").unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(reply.outcome, Outcome::Correct);
+        assert_eq!(reply.cooldown, None);
+        assert!(request.starts_with("POST /2022/day/1/answer "));
+        assert!(request.ends_with("level=2&answer=45000"));
+    }
+
+    #[test]
+    fn reports_bad_status() {
+        let (config, server) = mock_server("404 Not Found", "Not found");
+        let result = Client::new(config).get_input(25);
+        server.join().unwrap();
+
+        assert!(matches!(result, Err(super::Error::Status(404, _))));
+    }
+
+    #[test]
+    fn parses_outcomes() {
+        let wrap = |s: &str| format!("<main><article><p>{}</p></article></main>", s);
+
+        assert_eq!(
+            parse_outcome(&wrap(
+                "That's not the right answer; your answer is too high.  If you're stuck, ..."
+            )),
+            Outcome::TooHigh
+        );
+        assert_eq!(
+            parse_outcome(&wrap(
+                "That's not the right answer; your answer is too low."
+            )),
+            Outcome::TooLow
+        );
+        assert_eq!(
+            parse_outcome(&wrap("That's not the right answer.  If you're stuck, ...")),
+            Outcome::Incorrect
+        );
+        assert_eq!(
+            parse_outcome(&wrap("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 12s left to wait.")),
+            Outcome::Wait(72)
+        );
+        assert_eq!(
+            parse_outcome(&wrap(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            Outcome::AlreadySolved
+        );
+    }
+
+    #[test]
+    fn parses_cooldowns() {
+        let wrap = |s: &str| format!("<main><article><p>{}</p></article></main>", s);
+
+        assert_eq!(
+            parse_cooldown(&wrap(
+                "That's not the right answer.  Please wait one minute before trying again."
+            )),
+            Some(60)
+        );
+        assert_eq!(
+            parse_cooldown(&wrap("That's not the right answer; your answer is too low.  please wait 5 minutes before trying again.")),
+            Some(300)
+        );
+        assert_eq!(
+            parse_cooldown(&wrap(
+                "You gave an answer too recently.  You have 37s left to wait."
+            )),
+            Some(37)
+        );
+        assert_eq!(parse_cooldown(&wrap("That's the right answer!")), None);
+    }
+}
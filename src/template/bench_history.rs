@@ -0,0 +1,349 @@
+/// Module that keeps a history of benchmark runs, so timings survive README updates.
+/// Every timed `all` run appends one row per timed part to `data/benchmarks.csv`.
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::template::{input, readme_benchmarks::Timings};
+
+pub fn get_history_path() -> PathBuf {
+    input::get_data_dir().join("benchmarks.csv")
+}
+
+static HEADER: &str = "timestamp,commit,rustc,cpu,profile,day,part,nanos";
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+/// Describes the machine and build a run was recorded with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Environment {
+    pub commit: String,
+    pub rustc: String,
+    pub cpu: String,
+    pub profile: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub day: usize,
+    pub part: String,
+    pub nanos: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Run {
+    pub timestamp: u64,
+    pub env: Environment,
+    pub entries: Vec<Entry>,
+}
+
+impl Run {
+    pub fn get(&self, day: usize, part: &str) -> Option<f64> {
+        self.entries
+            .iter()
+            .find(|e| e.day == day && e.part == part)
+            .map(|e| e.nanos)
+    }
+
+    /// Total time of a day, summed over the parser and both parts.
+    pub fn day_total(&self, day: usize) -> f64 {
+        self.entries
+            .iter()
+            .filter(|e| e.day == day)
+            .map(|e| e.nanos)
+            .sum()
+    }
+}
+
+/// Relative change of one part between two runs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    pub day: usize,
+    pub part: String,
+    pub baseline: f64,
+    pub current: f64,
+    pub percent: f64,
+}
+
+impl Environment {
+    pub fn detect(is_release: bool) -> Self {
+        Environment {
+            commit: git_commit().unwrap_or_else(|| "unknown".into()),
+            rustc: rustc_version().unwrap_or_else(|| "unknown".into()),
+            cpu: cpu_model().unwrap_or_else(|| "unknown".into()),
+            profile: if is_release { "release" } else { "debug" }.into(),
+        }
+    }
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn git_commit() -> Option<String> {
+    let commit = command_output("git", &["rev-parse", "--short", "HEAD"])?;
+    let is_dirty =
+        !command_output("git", &["status", "--porcelain", "--untracked-files=no"])?.is_empty();
+
+    Some(if is_dirty {
+        format!("{}-dirty", commit)
+    } else {
+        commit
+    })
+}
+
+fn rustc_version() -> Option<String> {
+    command_output("rustc", &["--version"])
+}
+
+fn cpu_model() -> Option<String> {
+    let cpuinfo = fs::read_to_string("/proc/cpuinfo").ok()?;
+    cpuinfo
+        .lines()
+        .find(|l| l.starts_with("model name"))
+        .and_then(|l| l.split_once(':'))
+        .map(|(_, model)| model.trim().to_string())
+}
+
+/// Quote a CSV field if it contains a separator or quote.
+fn format_field(field: &str) -> String {
+    if field.contains(',') || field.contains('"') {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn split_row(line: &str) -> Vec<String> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+
+    fields.push(field);
+    fields
+}
+
+fn format_rows(run: &Run) -> String {
+    run.entries
+        .iter()
+        .map(|entry| {
+            [
+                run.timestamp.to_string(),
+                run.env.commit.clone(),
+                run.env.rustc.clone(),
+                run.env.cpu.clone(),
+                run.env.profile.clone(),
+                entry.day.to_string(),
+                entry.part.clone(),
+                entry.nanos.to_string(),
+            ]
+            .iter()
+            .map(|f| format_field(f))
+            .collect::<Vec<_>>()
+            .join(",")
+                + "\n"
+        })
+        .collect()
+}
+
+fn parse_rows(s: &str) -> Result<Vec<Run>, Error> {
+    let mut runs: Vec<Run> = vec![];
+
+    for (i, line) in s.lines().enumerate() {
+        if line.is_empty() || line == HEADER {
+            continue;
+        }
+
+        let fields = split_row(line);
+        let err = || Error::Parser(format!("malformed benchmark history on line {}.", i + 1));
+
+        if fields.len() != 8 {
+            return Err(err());
+        }
+
+        let timestamp = fields[0].parse().map_err(|_| err())?;
+        let env = Environment {
+            commit: fields[1].clone(),
+            rustc: fields[2].clone(),
+            cpu: fields[3].clone(),
+            profile: fields[4].clone(),
+        };
+        let entry = Entry {
+            day: fields[5].parse().map_err(|_| err())?,
+            part: fields[6].clone(),
+            nanos: fields[7].parse().map_err(|_| err())?,
+        };
+
+        match runs.last_mut() {
+            Some(run) if run.timestamp == timestamp && run.env == env => run.entries.push(entry),
+            _ => runs.push(Run {
+                timestamp,
+                env,
+                entries: vec![entry],
+            }),
+        }
+    }
+
+    Ok(runs)
+}
+
+/// Append the timings of a finished `all` run to the history file.
+pub fn append(timings: &[Timings], is_release: bool) -> Result<Run, Error> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let run = Run {
+        timestamp,
+        env: Environment::detect(is_release),
+        entries: timings
+            .iter()
+            .flat_map(|t| {
+                t.nanos.iter().map(|(part, nanos)| Entry {
+                    day: t.day,
+                    part: part.clone(),
+                    nanos: *nanos,
+                })
+            })
+            .collect(),
+    };
+
+    let path = get_history_path();
+    let is_new = fs::metadata(&path).is_err();
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+
+    if is_new {
+        writeln!(file, "{}", HEADER)?;
+    }
+
+    file.write_all(format_rows(&run).as_bytes())?;
+    Ok(run)
+}
+
+/// Read all recorded runs, oldest first.
+pub fn read() -> Result<Vec<Run>, Error> {
+    match fs::read_to_string(get_history_path()) {
+        Ok(s) => parse_rows(&s),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Find a run by timestamp or by (a prefix of) its git commit. Later runs win.
+pub fn find<'a>(runs: &'a [Run], selector: &str) -> Option<&'a Run> {
+    runs.iter()
+        .rev()
+        .find(|r| r.timestamp.to_string() == selector || r.env.commit.starts_with(selector))
+}
+
+/// Compare every part that was timed in both runs.
+pub fn compare(baseline: &Run, current: &Run) -> Vec<Change> {
+    current
+        .entries
+        .iter()
+        .filter_map(|entry| {
+            let before = baseline.get(entry.day, &entry.part)?;
+            Some(Change {
+                day: entry.day,
+                part: entry.part.clone(),
+                baseline: before,
+                current: entry.nanos,
+                percent: (entry.nanos - before) / before.max(1.0) * 100.0,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compare, find, format_rows, parse_rows, split_row, Entry, Environment, Run};
+
+    fn get_mock_run(timestamp: u64, commit: &str, nanos: f64) -> Run {
+        Run {
+            timestamp,
+            env: Environment {
+                commit: commit.into(),
+                rustc: "rustc 1.66.0".into(),
+                cpu: "Intel(R) Core(TM) i7-8700 CPU @ 3.20GHz, \"turbo\"".into(),
+                profile: "release".into(),
+            },
+            entries: vec![
+                Entry {
+                    day: 1,
+                    part: "Parser".into(),
+                    nanos: 100.0,
+                },
+                Entry {
+                    day: 1,
+                    part: "Part 1".into(),
+                    nanos,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn round_trips_runs() {
+        let runs = vec![get_mock_run(1, "abc", 10.0), get_mock_run(2, "def", 20.0)];
+        let csv: String = runs.iter().map(format_rows).collect();
+        assert_eq!(parse_rows(&csv).unwrap(), runs);
+    }
+
+    #[test]
+    fn splits_quoted_fields() {
+        assert_eq!(split_row("a,\"b,\"\"c\"\"\",d"), vec!["a", "b,\"c\"", "d"]);
+    }
+
+    #[test]
+    fn finds_runs_by_commit_or_timestamp() {
+        let runs = vec![
+            get_mock_run(1, "abc123", 10.0),
+            get_mock_run(2, "def456", 20.0),
+        ];
+        assert_eq!(find(&runs, "abc").unwrap().timestamp, 1);
+        assert_eq!(find(&runs, "2").unwrap().env.commit, "def456");
+        assert!(find(&runs, "xyz").is_none());
+    }
+
+    #[test]
+    fn compares_runs() {
+        let changes = compare(&get_mock_run(1, "a", 100.0), &get_mock_run(2, "b", 150.0));
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].percent, 0.0);
+        assert_eq!(changes[1].percent, 50.0);
+    }
+}
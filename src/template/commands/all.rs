@@ -1,31 +1,83 @@
-use std::io;
+use std::{io, process};
 
 use crate::template::{
-    readme_benchmarks::{self, Timings},
+    answers::Check,
+    bench_history,
+    readme_benchmarks::{self, Stats, Timings},
+    readme_progress::{self, Progress},
+    selection::{self, Selection},
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
 
-pub fn all_handler(is_release: bool, is_timed: bool) {
+pub fn all_handler(is_release: bool, is_timed: bool, timeout: Option<f64>, selection: Selection) {
     let mut timings: Vec<Timings> = vec![];
+    let mut mismatches = 0;
+    let mut timeouts: Vec<String> = vec![];
+    let mut failures: Vec<String> = vec![];
+    let mut status: Vec<(usize, bool)> = vec![];
+    let mut progress: Vec<Progress> = vec![];
 
-    (1..=25).for_each(|day| {
-        if day > 1 {
+    let days = selection.resolve(is_release);
+
+    days.iter().enumerate().for_each(|(i, &day)| {
+        if i > 0 {
             println!();
         }
 
         println!("{}Day {}{}", ANSI_BOLD, day, ANSI_RESET);
         println!("------");
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        let output = child_commands::run_solution(day, is_timed, is_release, timeout).unwrap();
 
-        if output.is_empty() {
+        if output.lines.is_empty() && !output.failed() {
             println!("Not solved.");
+            progress.push(Progress::from_checks(day, &[]));
         } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+            if !output.lines.is_empty() {
+                timings.push(child_commands::parse_exec_time(&output.lines, day));
+            }
+
+            let checks = child_commands::parse_checks(&output.lines);
+            progress.push(Progress::from_checks(day, &checks));
+
+            mismatches += checks
+                .iter()
+                .filter(|(_, check)| *check == Check::Incorrect)
+                .count();
+
+            status.push((day, output.passed()));
+
+            child_commands::parse_timeouts(&output.lines)
+                .into_iter()
+                .for_each(|part| timeouts.push(format!("Day {} part {}", day, part)));
+
+            // a panic, a parse error or a compile error.
+            if let Some(exit) = output.status.filter(|_| output.failed()) {
+                failures.push(format!("Day {} failed ({})", day, exit));
+            }
         }
     });
 
+    if days.is_empty() {
+        println!("No days selected.");
+    }
+
+    if let Err(e) = selection::update_status(&status) {
+        eprintln!(
+            "Failed to update \"{}\": {:?}",
+            selection::get_status_path().display(),
+            e
+        );
+    }
+
+    if selection.is_everything() {
+        match readme_progress::update(&progress) {
+            Ok(true) => println!("Successfully updated README with progress."),
+            Ok(false) => {}
+            Err(_) => eprintln!("Failed to update readme with progress."),
+        }
+    }
+
     if is_timed {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1000000_f64;
 
@@ -34,7 +86,18 @@ pub fn all_handler(is_release: bool, is_timed: bool) {
             ANSI_BOLD, ANSI_RESET, ANSI_ITALIC, total_millis, ANSI_RESET
         );
 
-        if is_release {
+        match bench_history::append(&timings, is_release) {
+            Ok(run) => println!(
+                "Recorded run {} in \"{}\".",
+                run.timestamp,
+                bench_history::get_history_path().display()
+            ),
+            Err(e) => eprintln!("Failed to record benchmark history: {:?}", e),
+        }
+
+        if is_release && !selection.is_everything() {
+            println!("Not updating README with benchmarks, only some days were selected.");
+        } else if is_release {
             match readme_benchmarks::update(timings, total_millis) {
                 Ok(_) => println!("Successfully updated README with benchmarks."),
                 Err(_) => {
@@ -43,6 +106,20 @@ pub fn all_handler(is_release: bool, is_timed: bool) {
             }
         }
     }
+
+    timeouts
+        .iter()
+        .for_each(|t| eprintln!("\n{} timed out.", t));
+
+    failures.iter().for_each(|f| eprintln!("\n{}.", f));
+
+    if mismatches > 0 {
+        eprintln!("\n{} part(s) did not match the stored answers.", mismatches);
+    }
+
+    if mismatches > 0 || !timeouts.is_empty() || !failures.is_empty() {
+        process::exit(1);
+    }
 }
 
 #[derive(Debug)]
@@ -65,26 +142,75 @@ pub fn get_path_for_bin(day: usize) -> String {
 
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
+pub mod child_commands {
     use super::{get_path_for_bin, Error};
+    use crate::template::answers::Check;
     use std::{
         io::{BufRead, BufReader},
         path::Path,
-        process::{Command, Stdio},
+        process::{Command, ExitStatus, Stdio},
         thread,
     };
 
+    /// The stdout lines of a day's binary and how it exited. `status` is `None` for days that are not scaffolded.
+    #[derive(Clone, Debug, Default)]
+    pub struct Output {
+        pub lines: Vec<String>,
+        pub status: Option<ExitStatus>,
+    }
+
+    impl Output {
+        /// Whether the binary failed, e.g. because it did not compile, could not parse its input or panicked.
+        pub fn failed(&self) -> bool {
+            self.status.is_some_and(|status| !status.success())
+        }
+
+        /// Whether the day passed: the binary succeeded, both parts match the stored answers and none timed out.
+        pub fn passed(&self) -> bool {
+            let checks = parse_checks(&self.lines);
+
+            !self.failed()
+                && checks.len() == 2
+                && checks.iter().all(|(_, check)| *check == Check::Correct)
+                && parse_timeouts(&self.lines).is_empty()
+        }
+    }
+
     /// Run the solution bin for a given day
     pub fn run_solution(
         day: usize,
         is_timed: bool,
         is_release: bool,
-    ) -> Result<Vec<String>, Error> {
+        timeout: Option<f64>,
+    ) -> Result<Output, Error> {
+        let mut flags = vec![];
+
+        if is_timed {
+            // mirror `--time` flag to child invocations.
+            flags.push("--time".to_string());
+        }
+
+        if let Some(timeout) = timeout {
+            flags.push("--timeout".to_string());
+            flags.push(timeout.to_string());
+        }
+
+        run_with_flags(day, is_release, &flags, true)
+    }
+
+    /// Run the solution bin for a given day with the given flags, and collect its output.
+    /// The output is forwarded to stdout as well if `echo` is set.
+    pub fn run_with_flags(
+        day: usize,
+        is_release: bool,
+        flags: &[String],
+        echo: bool,
+    ) -> Result<Output, Error> {
         let day_padded = format!("{:02}", day);
 
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+            return Ok(Output::default());
         }
 
         let mut args = vec!["run", "--quiet", "--bin", &day_padded];
@@ -93,11 +219,8 @@ mod child_commands {
             args.push("--release");
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
-        }
+        args.push("--");
+        args.extend(flags.iter().map(String::as_str));
 
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
@@ -121,14 +244,19 @@ mod child_commands {
 
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{}", line);
+            if echo {
+                println!("{}", line);
+            }
             output.push(line);
         }
 
         thread.join().unwrap();
-        cmd.wait()?;
+        let status = cmd.wait()?;
 
-        Ok(output)
+        Ok(Output {
+            lines: output,
+            status: Some(status),
+        })
     }
 
     pub fn parse_exec_time(output: &[String], day: usize) -> super::Timings {
@@ -137,9 +265,19 @@ mod child_commands {
             part_1: None,
             part_2: None,
             parser: None,
+            combined: None,
             total_nanos: 0_f64,
+            nanos: vec![],
+            stats: vec![],
+            memory: None,
         };
 
+        timings.memory = output
+            .iter()
+            .find_map(|l| l.strip_prefix("Memory: "))
+            .and_then(|l| l.split_whitespace().next())
+            .map(String::from);
+
         output
             .iter()
             .filter_map(|l| {
@@ -156,10 +294,12 @@ mod child_commands {
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                Some((part, timing_str, nanos, parse_stats(l)))
             })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
+            .for_each(|(part, timing_str, nanos, stats)| {
+                if part.contains("Combined") {
+                    timings.combined = Some(timing_str.into());
+                } else if part.contains("Part 1") {
                     timings.part_1 = Some(timing_str.into());
                 } else if part.contains("Part 2") {
                     timings.part_2 = Some(timing_str.into());
@@ -168,17 +308,57 @@ mod child_commands {
                 }
 
                 timings.total_nanos += nanos;
+                timings.nanos.push((part.trim().to_string(), nanos));
+                if let Some(stats) = stats {
+                    timings.stats.push((part.trim().to_string(), stats));
+                }
             });
 
         timings
     }
 
+    /// Collect the answer checks printed next to each part, e.g. `Part 1: 24000 ✓ (1.2ms)`.
+    /// Parts without a stored answer are reported as `Check::Unknown`.
+    pub fn parse_checks(output: &[String]) -> Vec<(u8, Check)> {
+        output
+            .iter()
+            .filter_map(|l| {
+                let (part, rest) = l.strip_prefix("Part ")?.split_once(':')?;
+                let part = part.parse().ok()?;
+
+                if rest.contains(Check::Incorrect.symbol()) {
+                    Some((part, Check::Incorrect))
+                } else if rest.contains(Check::Correct.symbol()) {
+                    Some((part, Check::Correct))
+                } else if rest.contains('✖') || rest.contains("timed out") {
+                    None
+                } else {
+                    Some((part, Check::Unknown))
+                }
+            })
+            .collect()
+    }
+
+    /// Collect the parts that were cancelled by `--timeout`.
+    pub fn parse_timeouts(output: &[String]) -> Vec<u8> {
+        output
+            .iter()
+            .filter_map(|l| {
+                let (part, rest) = l.strip_prefix("Part ")?.split_once(':')?;
+                if rest.contains("timed out") {
+                    part.parse().ok()
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
     fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
         s.split(postfix).next()?.parse().ok()
     }
 
     fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
         let str_timing = line
             .split(" samples)")
             .next()?
@@ -188,14 +368,36 @@ mod child_commands {
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
+        Some((str_timing, parse_duration(str_timing)?))
+    }
+
+    /// Parse a duration printed with `{:.1?}` into nanoseconds.
+    pub fn parse_duration(s: &str) -> Option<f64> {
+        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+        match s.trim() {
             s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
             s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
             s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1000000_f64),
             s => parse_to_float(s, "s").map(|x| x * 1000000000_f64),
-        }?;
+        }
+    }
+
+    /// Parse the sample count and the `[median 3.0µs ± 3.2µs]` suffix of a benched line.
+    fn parse_stats(line: &str) -> Option<super::Stats> {
+        let (timing, rest) = line.split_once(" samples)")?;
+        let samples = timing.rsplit('@').next()?.trim().parse().ok()?;
+        let (median, spread) = rest
+            .split("[median ")
+            .nth(1)?
+            .split(']')
+            .next()?
+            .split_once(" ± ")?;
 
-        Some((str_timing, parsed_timing))
+        Some(super::Stats {
+            samples,
+            median: median.trim().into(),
+            spread: spread.trim().into(),
+        })
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -214,7 +416,9 @@ mod child_commands {
 
     #[cfg(test)]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_checks, parse_exec_time, parse_timeouts, Output};
+        use crate::template::answers::Check;
+        use std::{os::unix::process::ExitStatusExt, process::ExitStatus};
 
         #[test]
         fn test_well_formed() {
@@ -222,7 +426,8 @@ mod child_commands {
                 &[
                     "Parser: ✓ (7.3µs @ 6579 samples)".into(),
                     "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples) [median 70.2ms ± 4.1ms]".into(),
+                    "Memory: 2.1MiB peak".into(),
                     "".into(),
                 ],
                 1,
@@ -231,6 +436,14 @@ mod child_commands {
             assert_eq!(res.parser.unwrap(), "7.3µs");
             assert_eq!(res.part_1.unwrap(), "74.13ns");
             assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.memory.unwrap(), "2.1MiB");
+            assert_eq!(res.nanos[0].0, "Parser");
+            assert_approx_eq!(res.nanos[1].1, 74.13_f64);
+            assert_eq!(res.stats.len(), 1);
+            assert_eq!(res.stats[0].0, "Part 2");
+            assert_eq!(res.stats[0].1.samples, 99999);
+            assert_eq!(res.stats[0].1.median, "70.2ms");
+            assert_eq!(res.stats[0].1.spread, "4.1ms");
         }
 
         #[test]
@@ -266,5 +479,78 @@ mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn test_combined() {
+            let output = [
+                "Parser: ✓ (1ms @ 10 samples)".into(),
+                "Combined: ✓ (3ms @ 10 samples)".into(),
+                "Part 1: 31 ✓ (combined)".into(),
+                "Part 2: 29 ✓ (combined)".into(),
+            ];
+            let res = parse_exec_time(&output, 1);
+            assert_approx_eq!(res.total_nanos, 4000000_f64);
+            assert_eq!(res.combined.unwrap(), "3ms");
+            assert!(res.part_1.is_none());
+            assert_eq!(
+                parse_checks(&output),
+                vec![(1, Check::Correct), (2, Check::Correct)]
+            );
+        }
+
+        #[test]
+        fn test_checks() {
+            let res = parse_checks(&[
+                "Parser: ✓ (1ms)".into(),
+                "Part 1: 24000 ✓ (74.13ns @ 100000 samples)".into(),
+                "Part 2: 44000 ✗ expected 45000 (1ms)".into(),
+            ]);
+            assert_eq!(res, vec![(1, Check::Correct), (2, Check::Incorrect)]);
+        }
+
+        #[test]
+        fn test_timeouts() {
+            let res = parse_timeouts(&[
+                "Part 1: 10 (1ms)".into(),
+                "Part 2: timed out after 10.0s".into(),
+            ]);
+            assert_eq!(res, vec![2]);
+        }
+
+        #[test]
+        fn test_checks_unknown_and_missing() {
+            let res = parse_checks(&["Part 1: 10 (1ms)".into(), "Part 2: ✖        ".into()]);
+            assert_eq!(res, vec![(1, Check::Unknown)]);
+        }
+
+        #[test]
+        fn test_failing_child() {
+            // part 2 panicked after part 1 was printed.
+            let lines: Vec<String> = vec!["Parser: ✓ (1ms)".into(), "Part 1: 24000 ✓ (1ms)".into()];
+            assert_eq!(parse_checks(&lines), vec![(1, Check::Correct)]);
+
+            let failed = Output {
+                lines: lines.clone(),
+                status: Some(ExitStatus::from_raw(101 << 8)),
+            };
+            assert!(failed.failed());
+            assert!(!failed.passed());
+
+            // a day that fails even with both answers printed still fails.
+            let mut lines = lines;
+            lines.push("Part 2: 45000 ✓ (1ms)".into());
+            let failed = Output {
+                lines: lines.clone(),
+                status: Some(ExitStatus::from_raw(1 << 8)),
+            };
+            assert!(!failed.passed());
+
+            let passed = Output {
+                lines,
+                status: Some(ExitStatus::from_raw(0)),
+            };
+            assert!(passed.passed());
+            assert!(!Output::default().failed());
+        }
     }
 }
@@ -0,0 +1,224 @@
+/// `cargo bench` harness over every scaffolded day, see `benches/days.rs`. Days are selected like in `all` and run
+/// through the same child commands, in release mode with `--time`. Results are printed in the format of libtest's
+/// `#[bench]`, which tools for comparing `cargo bench` runs understand, and written to a CSV file.
+///
+/// ```sh
+/// cargo bench --bench days -- --days 1-5 --part 2 --csv target/days.csv
+/// ```
+use std::{fs, io, path::Path};
+
+use crate::template::{
+    commands::all::child_commands, readme_benchmarks::Timings, selection::Selection, ANSI_BOLD,
+    ANSI_RESET,
+};
+
+pub static CSV_PATH: &str = "target/bench/days.csv";
+static CSV_HEADER: &str = "day,label,samples,mean_ns,median_ns,spread_ns";
+
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    pub selection: Selection,
+    /// Only bench this part. The parser still runs, but is not reported, and neither are days that solve both parts
+    /// in one `Combined` run.
+    pub part: Option<u8>,
+    /// Where to write the results, `CSV_PATH` by default.
+    pub csv: Option<String>,
+}
+
+/// Timing of one label of a day, in nanoseconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Measurement {
+    pub day: usize,
+    /// `Parser`, `Part 1`, `Part 2` or `Combined`.
+    pub label: String,
+    pub samples: u128,
+    pub mean: f64,
+    pub median: f64,
+    pub spread: f64,
+}
+
+impl Measurement {
+    /// The name of the benchmark, e.g. `day05/part_1`.
+    pub fn name(&self) -> String {
+        format!(
+            "day{:02}/{}",
+            self.day,
+            self.label.to_lowercase().replace(' ', "_")
+        )
+    }
+}
+
+/// The measurements of a day's run. Labels that were not benched count as a single sample.
+pub fn measurements(timings: &Timings) -> Vec<Measurement> {
+    timings
+        .nanos
+        .iter()
+        .map(|(label, mean)| {
+            let stats = timings.stats.iter().find(|(l, _)| l == label);
+            let parse = |s: &str| child_commands::parse_duration(s).unwrap_or(*mean);
+
+            Measurement {
+                day: timings.day,
+                label: label.clone(),
+                samples: stats.map(|(_, s)| s.samples).unwrap_or(1),
+                mean: *mean,
+                median: stats.map(|(_, s)| parse(&s.median)).unwrap_or(*mean),
+                spread: stats.map(|(_, s)| parse(&s.spread)).unwrap_or(0.0),
+            }
+        })
+        .collect()
+}
+
+/// Group the digits of a number by thousands, as libtest does.
+// `is_multiple_of` needs Rust 1.87.
+#[allow(clippy::manual_is_multiple_of)]
+fn group_digits(n: u128) -> String {
+    let digits = n.to_string();
+    let mut grouped = String::new();
+
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+
+    grouped
+}
+
+/// A line in the format of libtest's `#[bench]`, with the median as time per iteration.
+fn format_bench(m: &Measurement) -> String {
+    format!(
+        "test {} ... bench: {:>11} ns/iter (+/- {})",
+        m.name(),
+        group_digits(m.median.round() as u128),
+        group_digits(m.spread.round() as u128)
+    )
+}
+
+fn format_csv(measurements: &[Measurement]) -> String {
+    let mut lines = vec![CSV_HEADER.to_string()];
+
+    lines.extend(measurements.iter().map(|m| {
+        format!(
+            "{},{},{},{:.0},{:.0},{:.0}",
+            m.day, m.label, m.samples, m.mean, m.median, m.spread
+        )
+    }));
+
+    lines.join("\n") + "\n"
+}
+
+fn write_csv(path: &str, measurements: &[Measurement]) -> Result<(), io::Error> {
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, format_csv(measurements))
+}
+
+pub fn bench_handler(options: Options) {
+    let Options {
+        selection,
+        part,
+        csv,
+    } = options;
+
+    let mut flags = vec!["--time".to_string()];
+    if let Some(part) = part {
+        flags.push("--part".to_string());
+        flags.push(part.to_string());
+    }
+
+    let mut results: Vec<Measurement> = vec![];
+
+    for day in selection.resolve(true) {
+        let output = match child_commands::run_with_flags(day, true, &flags, false) {
+            Ok(output) => output,
+            Err(e) => {
+                eprintln!("Failed to run day {}: {:?}", day, e);
+                continue;
+            }
+        };
+
+        if output.lines.is_empty() {
+            continue;
+        }
+
+        let timings = child_commands::parse_exec_time(&output.lines, day);
+        measurements(&timings)
+            .into_iter()
+            // the parser always runs, and a combined run times both parts, so both are only of interest when benching
+            // whole days.
+            .filter(|m| part.is_none() || (m.label != "Parser" && m.label != "Combined"))
+            .for_each(|m| {
+                println!("{}", format_bench(&m));
+                results.push(m);
+            });
+    }
+
+    println!(
+        "\n{}bench result:{} {} measured",
+        ANSI_BOLD,
+        ANSI_RESET,
+        results.len()
+    );
+
+    let path = csv.unwrap_or_else(|| CSV_PATH.to_string());
+    match write_csv(&path, &results) {
+        Ok(_) => println!("Wrote results to \"{}\".", path),
+        Err(e) => eprintln!("Failed to write \"{}\": {}", path, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_bench, format_csv, measurements};
+    use crate::template::readme_benchmarks::{Stats, Timings};
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            day: 5,
+            part_1: Some("1.2µs".into()),
+            part_2: Some("1.5ms".into()),
+            parser: None,
+            combined: None,
+            total_nanos: 1501200.0,
+            nanos: vec![("Part 1".into(), 1200.0), ("Part 2".into(), 1500000.0)],
+            stats: vec![(
+                "Part 2".into(),
+                Stats {
+                    samples: 667,
+                    median: "1.4ms".into(),
+                    spread: "25.5µs".into(),
+                },
+            )],
+            memory: None,
+        }
+    }
+
+    #[test]
+    fn formats_like_libtest() {
+        let lines: Vec<String> = measurements(&get_mock_timings())
+            .iter()
+            .map(format_bench)
+            .collect();
+
+        assert_eq!(
+            lines,
+            vec![
+                "test day05/part_1 ... bench:       1,200 ns/iter (+/- 0)",
+                "test day05/part_2 ... bench:   1,400,000 ns/iter (+/- 25,500)",
+            ]
+        );
+    }
+
+    #[test]
+    fn exports_csv() {
+        assert_eq!(
+            format_csv(&measurements(&get_mock_timings())),
+            "day,label,samples,mean_ns,median_ns,spread_ns\n\
+             5,Part 1,1,1200,1200,0\n\
+             5,Part 2,667,1500000,1400000,25500\n"
+        );
+    }
+}
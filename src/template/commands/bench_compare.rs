@@ -0,0 +1,101 @@
+use std::{process, time::Duration};
+
+use crate::template::{
+    bench_history::{self, Run},
+    ANSI_BOLD, ANSI_RESET,
+};
+
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos as u64))
+}
+
+fn describe(run: &Run) -> String {
+    format!(
+        "{} ({}, {}, {})",
+        run.timestamp, run.env.commit, run.env.profile, run.env.rustc
+    )
+}
+
+fn select<'a>(runs: &'a [Run], selector: &Option<String>, fallback: Option<&'a Run>) -> &'a Run {
+    let run = match selector {
+        Some(selector) => bench_history::find(runs, selector),
+        None => fallback,
+    };
+
+    match run {
+        Some(run) => run,
+        None => {
+            match selector {
+                Some(selector) => eprintln!("No benchmark run matches \"{}\".", selector),
+                None => eprintln!(
+                    "Not enough runs in \"{}\" to compare. Run `cargo all --release --time` first.",
+                    bench_history::get_history_path().display()
+                ),
+            }
+            process::exit(1);
+        }
+    }
+}
+
+/// Compare two runs from the benchmark history and flag every part that got slower than `threshold` percent.
+/// Defaults to comparing the latest run with the one before it.
+pub fn bench_compare_handler(baseline: Option<String>, current: Option<String>, threshold: f64) {
+    let runs = match bench_history::read() {
+        Ok(runs) => runs,
+        Err(e) => {
+            eprintln!("Failed to read benchmark history: {:?}", e);
+            process::exit(1);
+        }
+    };
+
+    let current = select(&runs, &current, runs.last());
+    let previous = runs.iter().rev().find(|r| r.timestamp < current.timestamp);
+    let baseline = select(&runs, &baseline, previous);
+
+    println!("Baseline: {}", describe(baseline));
+    println!("Current:  {}", describe(current));
+
+    if baseline.env.cpu != current.env.cpu || baseline.env.profile != current.env.profile {
+        println!("Warning: runs were recorded on different machines or build profiles.");
+    }
+
+    println!();
+
+    let changes = bench_history::compare(baseline, current);
+    let mut regressions = 0;
+
+    for change in &changes {
+        let marker = if change.percent > threshold {
+            regressions += 1;
+            "✗ regression"
+        } else if change.percent < -threshold {
+            "✓ faster"
+        } else {
+            ""
+        };
+
+        let line = format!(
+            "Day {:>2} {:<6} {:>10} → {:<10} {:>+8.1}% {}",
+            change.day,
+            change.part,
+            format_nanos(change.baseline),
+            format_nanos(change.current),
+            change.percent,
+            marker
+        );
+        println!("{}", line.trim_end());
+    }
+
+    println!(
+        "\n{}{} regression(s){} above {}% in {} compared parts.",
+        ANSI_BOLD,
+        regressions,
+        ANSI_RESET,
+        threshold,
+        changes.len()
+    );
+
+    if regressions > 0 {
+        process::exit(1);
+    }
+}
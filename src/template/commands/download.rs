@@ -1,14 +1,49 @@
-use crate::template::aoc_cli;
-use std::process;
+use crate::template::{aoc_client, input};
+use std::{fs, path::Path, process};
 
-pub fn download_handler(day: u8) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+fn write_file(path: &Path, content: &str) {
+    if let Some(parent) = path.parent() {
+        if let Err(e) = fs::create_dir_all(parent) {
+            eprintln!("Failed to create directory \"{}\": {}", parent.display(), e);
+            process::exit(1);
+        }
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {}", e);
+    if let Err(e) = fs::write(path, content) {
+        eprintln!("Failed to write \"{}\": {}", path.display(), e);
         process::exit(1);
+    }
+}
+
+pub fn download_handler(day: u8) {
+    let client = match aoc_client::Client::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Failed to download: {}", e);
+            process::exit(1);
+        }
     };
+
+    let (input, puzzle) = match (client.get_input(day), client.get_puzzle(day)) {
+        (Ok(input), Ok(puzzle)) => (input, puzzle),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("Failed to download day {}: {}", day, e);
+            process::exit(1);
+        }
+    };
+
+    let input_path = input::get_data_path("inputs", day);
+    let puzzle_path = input::get_day_path("puzzles", day, "md");
+
+    write_file(&input_path, &input);
+    write_file(&puzzle_path, &puzzle);
+
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        input_path.display()
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
 }
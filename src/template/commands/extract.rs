@@ -0,0 +1,106 @@
+use std::{fs, process};
+
+use crate::template::{
+    answers::Answers,
+    examples::{self, Extracted},
+    input, ANSI_BOLD, ANSI_RESET,
+};
+
+/// Read and parse `data/puzzles/DD.md`.
+pub fn read_puzzle(day: u8) -> Option<Extracted> {
+    let puzzle_path = input::get_day_path("puzzles", day, "md");
+    let markdown = fs::read_to_string(puzzle_path).ok()?;
+    Some(examples::extract(&markdown))
+}
+
+/// Write the chosen example and its answers. Existing files are only replaced if they are empty or `overwrite` is set,
+/// and then only the root answers of the manifest change.
+pub fn write_example(
+    day: u8,
+    example: &str,
+    answers: &Answers,
+    overwrite: bool,
+) -> Result<(), String> {
+    let example_path = examples::get_example_path(day);
+    let answers_path = examples::get_example_answers_path(day);
+
+    for path in [&example_path, &answers_path] {
+        let is_empty = fs::read_to_string(path).map_or(true, |s| s.trim().is_empty());
+        if !overwrite && !is_empty {
+            return Err(format!(
+                "\"{}\" already exists, pass --overwrite to replace it.",
+                path.display()
+            ));
+        }
+    }
+
+    if let Some(parent) = example_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    fs::write(&example_path, example).map_err(|e| e.to_string())?;
+    println!("Wrote example to \"{}\"", example_path.display());
+
+    examples::write_answers(day, answers).map_err(|e| e.to_string())?;
+    println!("Wrote example answers to \"{}\"", answers_path.display());
+
+    Ok(())
+}
+
+/// List the code blocks of a puzzle, propose the example input and store it together with the example answers.
+pub fn extract_handler(day: u8, block: Option<usize>, overwrite: bool) {
+    let extracted = match read_puzzle(day) {
+        Some(extracted) => extracted,
+        None => {
+            eprintln!(
+                "Could not read \"{}\", run `cargo download {}` first.",
+                input::get_day_path("puzzles", day, "md").display(),
+                day
+            );
+            process::exit(1);
+        }
+    };
+
+    for (i, content) in extracted.blocks.iter().enumerate() {
+        let marker = if Some(i) == extracted.proposed {
+            " (proposed)"
+        } else {
+            ""
+        };
+        println!(
+            "{}Block {}{}{}: {} line(s)",
+            ANSI_BOLD,
+            i,
+            marker,
+            ANSI_RESET,
+            content.lines().count()
+        );
+        content
+            .lines()
+            .take(3)
+            .for_each(|line| println!("    {}", line));
+    }
+
+    println!(
+        "Part 1: {}",
+        extracted.answers.part_one.as_deref().unwrap_or("?")
+    );
+    println!(
+        "Part 2: {}",
+        extracted.answers.part_two.as_deref().unwrap_or("?")
+    );
+    println!("---");
+
+    let index = match block.or(extracted.proposed) {
+        Some(index) if index < extracted.blocks.len() => index,
+        _ => {
+            eprintln!("No example block found, pick one with --block <n>.");
+            process::exit(1);
+        }
+    };
+
+    if let Err(e) = write_example(day, &extracted.blocks[index], &extracted.answers, overwrite) {
+        eprintln!("Failed to write example: {}", e);
+        process::exit(1);
+    }
+}
@@ -1,5 +1,10 @@
 pub mod all;
+pub mod bench;
+pub mod bench_compare;
 pub mod download;
+pub mod extract;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod verify;
+pub mod watch;
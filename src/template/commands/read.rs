@@ -1,15 +1,31 @@
-use std::process;
+use std::{fs, process};
 
-use crate::template::aoc_cli;
+use crate::template::{aoc_client, input};
 
+/// Print the puzzle description, refreshing `data/puzzles/DD.md` so newly unlocked parts show up.
 pub fn read_handler(day: u8) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+    let client = match aoc_client::Client::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Failed to read puzzle: {}", e);
+            process::exit(1);
+        }
+    };
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {}", e);
-        process::exit(1);
+    let puzzle = match client.get_puzzle(day) {
+        Ok(puzzle) => puzzle,
+        Err(e) => {
+            eprintln!("Failed to read day {}: {}", day, e);
+            process::exit(1);
+        }
     };
+
+    let puzzle_path = input::get_day_path("puzzles", day, "md");
+    if let Err(e) = fs::create_dir_all(input::get_data_dir().join("puzzles"))
+        .and_then(|_| fs::write(&puzzle_path, &puzzle))
+    {
+        eprintln!("Failed to write \"{}\": {}", puzzle_path.display(), e);
+    }
+
+    println!("{}", puzzle);
 }
@@ -1,9 +1,12 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
+use crate::template::{commands::extract, input};
+
 const MODULE_TEMPLATE: &str = r###"type Input<'a> = Vec<&'a str>;
 
 fn parse(input: &str) -> Input {
@@ -20,21 +23,10 @@ pub fn part_two(input: Input) -> Option<u32> {
 
 advent_of_code::main!(DAY);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(parse(&advent_of_code::template::read_file("examples", DAY)));
-        assert_eq!(result, None);
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(parse(&advent_of_code::template::read_file("examples", DAY)));
-        assert_eq!(result, None);
-    }
+advent_of_code::tests! {
+    day: DAY,
+    part_one: None,
+    part_two: None,
 }
 "###;
 
@@ -42,15 +34,18 @@ fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
     OpenOptions::new().write(true).create(true).open(path)
 }
 
 pub fn scaffold_handler(day: u8) {
     let day_padded = format!("{:02}", day);
 
-    let input_path = format!("data/inputs/{}.txt", day_padded);
-    let example_path = format!("data/examples/{}.txt", day_padded);
+    let input_path = input::get_data_path("inputs", day);
+    let example_path = input::get_data_path("examples", day);
     let module_path = format!("src/bin/{}.rs", day_padded);
 
     let mut file = match safe_create_file(&module_path) {
@@ -73,7 +68,7 @@ pub fn scaffold_handler(day: u8) {
 
     match create_file(&input_path) {
         Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+            println!("Created empty input file \"{}\"", input_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create input file: {}", e);
@@ -83,7 +78,7 @@ pub fn scaffold_handler(day: u8) {
 
     match create_file(&example_path) {
         Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+            println!("Created empty example file \"{}\"", example_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create example file: {}", e);
@@ -91,9 +86,31 @@ pub fn scaffold_handler(day: u8) {
         }
     }
 
+    if let Some(extracted) = extract::read_puzzle(day) {
+        if let Some(index) = extracted.proposed {
+            if let Err(e) =
+                extract::write_example(day, &extracted.blocks[index], &extracted.answers, false)
+            {
+                eprintln!("Failed to extract example: {}", e);
+            }
+        }
+    }
+
     println!("---");
     println!(
         "🎄 Type `cargo solve {}` to run your solution.",
         &day_padded
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::MODULE_TEMPLATE;
+    use crate::template::commands::solve::asserts_placeholder;
+
+    #[test]
+    fn scaffolds_placeholder_assertions() {
+        assert!(asserts_placeholder(MODULE_TEMPLATE, 1));
+        assert!(asserts_placeholder(MODULE_TEMPLATE, 2));
+    }
+}
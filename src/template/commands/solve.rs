@@ -1,6 +1,133 @@
-use std::process::{Command, Stdio};
+use std::{
+    fs,
+    process::{Command, Stdio},
+};
+
+use crate::template::commands::all::get_path_for_bin;
+
+fn part_name(part: u8) -> &'static str {
+    if part == 1 {
+        "part_one"
+    } else {
+        "part_two"
+    }
+}
+
+/// Whether `tests!` still expects `None` for the part, which passes for an unsolved part.
+pub(crate) fn asserts_placeholder(source: &str, part: u8) -> bool {
+    let name = part_name(part);
+
+    source
+        .lines()
+        .map(str::trim)
+        .any(|line| line.starts_with(name) && line.trim_end_matches(',').ends_with(": None"))
+}
+
+/// Sum up the passed and failed tests of every `test result:` line in the output of `cargo test`.
+pub(crate) fn count_tests(stdout: &str) -> (usize, usize) {
+    let count = |suffix: &str| {
+        stdout
+            .lines()
+            .filter_map(|line| line.strip_prefix("test result: "))
+            .flat_map(|line| line.split("; "))
+            .filter_map(|field| {
+                field
+                    .trim_start_matches("ok. ")
+                    .trim_start_matches("FAILED. ")
+                    .strip_suffix(suffix)
+            })
+            .filter_map(|count| count.parse::<usize>().ok())
+            .sum::<usize>()
+    };
+
+    (count(" passed"), count(" failed"))
+}
+
+/// Run the day's tests for one part, i.e. every test whose name contains `part_one` or `part_two`.
+/// Called by the runner before it submits a part.
+pub(crate) fn check_examples(day: u8, part: u8, release: bool) -> Result<(), String> {
+    let source = fs::read_to_string(get_path_for_bin(day.into())).map_err(|e| e.to_string())?;
+    if asserts_placeholder(&source, part) {
+        return Err(format!(
+            "the {} test still asserts `None`.",
+            part_name(part)
+        ));
+    }
+
+    let mut cmd_args = vec![
+        "test".to_string(),
+        "--quiet".to_string(),
+        "--bin".to_string(),
+        format!("{:02}", day),
+    ];
+
+    if release {
+        cmd_args.push("--release".to_string());
+    }
+
+    cmd_args.push("--".to_string());
+    cmd_args.push(part_name(part).to_string());
+
+    let output = Command::new("cargo")
+        .args(&cmd_args)
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    if !output.status.success() {
+        print!("{}", stdout);
+        return Err("example tests are failing.".into());
+    }
+
+    let (passed, _) = count_tests(&stdout);
+
+    if passed == 0 {
+        return Err(format!("no tests found for {}.", part_name(part)));
+    }
+
+    println!("{} example test(s) passed for part {}.", passed, part);
+    Ok(())
+}
+
+/// Flags of `cargo solve`, most of which are passed on to the day's binary.
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    pub release: bool,
+    pub time: bool,
+    pub submit_part: Option<u8>,
+    pub timeout: Option<f64>,
+    pub alloc: bool,
+    /// Print the timing spans of each part.
+    pub spans: bool,
+    /// Run the variants registered with `variants!` instead of the parts.
+    pub variants: bool,
+    /// Record the frames of `viz!` in this format: `ansi`, `ppm` or `gif`.
+    pub viz: Option<String>,
+    /// Submit even if the example tests fail.
+    pub force: bool,
+    /// `key=value` overrides of the day's params.
+    pub params: Vec<String>,
+    /// Read the input from this file instead of `data/inputs`, `-` for stdin.
+    pub input: Option<String>,
+}
+
+pub fn solve_handler(day: u8, options: Options) {
+    let Options {
+        release,
+        time,
+        submit_part,
+        timeout,
+        alloc,
+        spans,
+        variants,
+        viz,
+        force,
+        params,
+        input,
+    } = options;
 
-pub fn solve_handler(day: u8, release: bool, time: bool, submit_part: Option<u8>) {
     let day_padded = format!("{:02}", day);
 
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day_padded];
@@ -9,6 +136,16 @@ pub fn solve_handler(day: u8, release: bool, time: bool, submit_part: Option<u8>
         cmd_args.push("--release".to_string());
     }
 
+    if alloc {
+        cmd_args.push("--features".to_string());
+        cmd_args.push("alloc-profile".to_string());
+    }
+
+    if viz.is_some() {
+        cmd_args.push("--features".to_string());
+        cmd_args.push("viz".to_string());
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -16,10 +153,42 @@ pub fn solve_handler(day: u8, release: bool, time: bool, submit_part: Option<u8>
         cmd_args.push(submit_part.to_string())
     }
 
+    if force {
+        cmd_args.push("--force".to_string());
+    }
+
     if time {
         cmd_args.push("--time".to_string());
     }
 
+    if spans {
+        cmd_args.push("--spans".to_string());
+    }
+
+    if variants {
+        cmd_args.push("--variants".to_string());
+    }
+
+    if let Some(viz) = viz {
+        cmd_args.push("--viz".to_string());
+        cmd_args.push(viz);
+    }
+
+    if let Some(timeout) = timeout {
+        cmd_args.push("--timeout".to_string());
+        cmd_args.push(timeout.to_string());
+    }
+
+    for param in params {
+        cmd_args.push("--param".to_string());
+        cmd_args.push(param);
+    }
+
+    if let Some(input) = input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input);
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -29,3 +198,28 @@ pub fn solve_handler(day: u8, release: bool, time: bool, submit_part: Option<u8>
 
     cmd.wait().unwrap();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{asserts_placeholder, count_tests};
+
+    #[test]
+    fn detects_placeholder_assertions() {
+        let source = r#"
+advent_of_code::tests! {
+    day: 1,
+    part_one: Some(24000),
+    part_two("01/other.txt"): None,
+}
+"#;
+        assert!(!asserts_placeholder(source, 1));
+        assert!(asserts_placeholder(source, 2));
+    }
+
+    #[test]
+    fn counts_tests() {
+        let stdout = "running 3 tests\n...\ntest result: ok. 3 passed; 0 failed; 2 ignored; 0 measured; 0 filtered out; finished in 0.00s\n\ntest result: FAILED. 1 passed; 2 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.01s\n";
+        assert_eq!(count_tests(stdout), (4, 2));
+        assert_eq!(count_tests("error[E0308]: mismatched types"), (0, 0));
+    }
+}
@@ -0,0 +1,97 @@
+use std::{path::Path, process};
+
+use crate::template::{
+    answers::{self, Check},
+    commands::all::{child_commands, get_path_for_bin},
+    selection, ANSI_BOLD, ANSI_RESET,
+};
+
+/// Run every solved day against its real input and compare each part with the stored answers.
+pub fn verify_handler(is_release: bool, timeout: Option<f64>) {
+    let mut failures: Vec<String> = vec![];
+    let mut correct = 0;
+    let mut unknown = 0;
+    let mut status: Vec<(usize, bool)> = vec![];
+
+    for day in 1..=25 {
+        if !Path::new(&get_path_for_bin(day)).exists() {
+            continue;
+        }
+
+        println!("{}Day {}{}", ANSI_BOLD, day, ANSI_RESET);
+        println!("------");
+
+        let output = match child_commands::run_solution(day, false, is_release, timeout) {
+            Ok(output) => output,
+            Err(e) => {
+                failures.push(format!("Day {}: could not run solution ({:?})", day, e));
+                continue;
+            }
+        };
+
+        let checks = child_commands::parse_checks(&output.lines);
+        let failures_before = failures.len();
+
+        if let Some(exit) = output.status.filter(|_| output.failed()) {
+            failures.push(format!("Day {}: failed ({})", day, exit));
+        }
+
+        for part in child_commands::parse_timeouts(&output.lines) {
+            failures.push(format!("Day {} part {}: timed out", day, part));
+        }
+
+        if checks.is_empty() {
+            failures.push(format!("Day {}: no results", day));
+        }
+
+        for &(part, check) in &checks {
+            match check {
+                Check::Correct => correct += 1,
+                Check::Unknown => unknown += 1,
+                Check::Incorrect => {
+                    failures.push(format!("Day {} part {}: does not match", day, part))
+                }
+            }
+        }
+
+        status.push((
+            day,
+            failures.len() == failures_before
+                && checks.len() == 2
+                && checks.iter().all(|(_, check)| *check == Check::Correct),
+        ));
+
+        if let Ok(answers) = answers::read(day as u8) {
+            if answers.part_one.is_none() || answers.part_two.is_none() {
+                println!(
+                    "No complete answers stored in \"{}\".",
+                    answers::get_answers_path(day as u8).display()
+                );
+            }
+        }
+
+        println!();
+    }
+
+    if let Err(e) = selection::update_status(&status) {
+        eprintln!(
+            "Failed to update \"{}\": {:?}",
+            selection::get_status_path().display(),
+            e
+        );
+    }
+
+    println!(
+        "{}Verified:{} {} correct, {} failing, {} without a stored answer.",
+        ANSI_BOLD,
+        ANSI_RESET,
+        correct,
+        failures.len(),
+        unknown
+    );
+
+    if !failures.is_empty() {
+        failures.iter().for_each(|f| eprintln!("✗ {}", f));
+        process::exit(1);
+    }
+}
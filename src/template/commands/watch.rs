@@ -0,0 +1,218 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::{commands::solve::count_tests, input, ANSI_BOLD, ANSI_RESET};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const ANSI_CLEAR: &str = "\x1b[2J\x1b[H";
+
+/// Every file under `dir`, skipping the binaries of other days in `bin_dir`.
+fn source_files(dir: &Path, bin_dir: &Path, day: u8, paths: &mut Vec<PathBuf>) {
+    let own_bin = bin_dir.join(format!("{:02}.rs", day));
+
+    let mut entries: Vec<PathBuf> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .collect(),
+        Err(_) => return,
+    };
+    entries.sort();
+
+    for path in entries {
+        if path.parent() == Some(bin_dir) && path != own_bin {
+            continue;
+        }
+
+        if path.is_dir() {
+            source_files(&path, bin_dir, day, paths);
+        } else {
+            paths.push(path);
+        }
+    }
+}
+
+/// The files a day's tests and solution depend on: the crate's sources except other days' binaries, its build
+/// script and manifest, and the day's files in the data directory. Example inputs of a manifest live in
+/// `data/examples/DD/`.
+fn watched_paths(day: u8) -> Vec<PathBuf> {
+    let crate_dir = input::get_crate_dir();
+    let src_dir = crate_dir.join("src");
+
+    let mut paths = vec![];
+    source_files(&src_dir, &src_dir.join("bin"), day, &mut paths);
+
+    paths.extend([
+        crate_dir.join("build.rs"),
+        crate_dir.join("Cargo.toml"),
+        input::get_data_path("inputs", day),
+        input::get_data_path("examples", day),
+        input::get_day_path("examples", day, "toml"),
+        input::get_day_path("answers", day, "toml"),
+    ]);
+
+    let examples_dir = input::get_data_dir()
+        .join("examples")
+        .join(format!("{:02}", day));
+    if let Ok(entries) = fs::read_dir(examples_dir) {
+        paths.extend(entries.filter_map(|entry| Some(entry.ok()?.path())));
+    }
+
+    paths
+}
+
+/// Modification times of the watched files, `None` for files that do not exist (yet).
+fn snapshot(day: u8) -> Vec<(PathBuf, Option<SystemTime>)> {
+    watched_paths(day)
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+fn cargo(args: &[&str]) -> Option<Output> {
+    match Command::new("cargo").args(args).output() {
+        Ok(output) => Some(output),
+        Err(e) => {
+            eprintln!("Failed to run cargo: {}", e);
+            None
+        }
+    }
+}
+
+/// Print the last lines of the compiler output, which hold the errors.
+fn print_tail(stderr: &str, lines: usize) {
+    let all: Vec<&str> = stderr.lines().collect();
+    all[all.len().saturating_sub(lines)..]
+        .iter()
+        .for_each(|line| println!("{}", line));
+}
+
+/// Run the day's tests and print a one-line summary plus the names of failing tests.
+/// Returns whether the day compiled, i.e. whether running the solution makes sense.
+fn run_tests(day: u8, release: bool) -> bool {
+    let day_padded = format!("{:02}", day);
+    let mut args = vec!["test", "--quiet", "--bin", &day_padded];
+    if release {
+        args.push("--release");
+    }
+
+    let output = match cargo(&args) {
+        Some(output) => output,
+        None => return false,
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (passed, failed) = count_tests(&stdout);
+
+    if !stdout.contains("test result: ") {
+        println!("Tests: ✖ does not compile");
+        print_tail(&String::from_utf8_lossy(&output.stderr), 20);
+        return false;
+    }
+
+    if failed == 0 {
+        println!("Tests: {}✓{} {} passed", ANSI_BOLD, ANSI_RESET, passed);
+    } else {
+        println!(
+            "Tests: {}✖{} {} passed, {} failed",
+            ANSI_BOLD, ANSI_RESET, passed, failed
+        );
+        stdout
+            .lines()
+            .filter_map(|line| line.strip_prefix("---- ")?.strip_suffix(" stdout ----"))
+            .for_each(|name| println!("    {}", name));
+    }
+
+    true
+}
+
+/// Run the day's solution and print its output without the intermediate results.
+fn run_solution(day: u8, release: bool) {
+    let day_padded = format!("{:02}", day);
+    let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    if release {
+        args.push("--release");
+    }
+
+    let output = match cargo(&args) {
+        Some(output) => output,
+        None => return,
+    };
+
+    // parts print a preliminary result and overwrite it with `\r` once they are done.
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.rsplit('\r').next())
+        .for_each(|line| println!("{}", line));
+
+    if !output.status.success() {
+        print_tail(&String::from_utf8_lossy(&output.stderr), 10);
+    }
+}
+
+/// Re-run a day's tests and solution whenever its source or data files change.
+pub fn watch_handler(day: u8, release: bool) {
+    let mut last = None;
+
+    loop {
+        let current = snapshot(day);
+
+        if last.as_ref() != Some(&current) {
+            let changed: Vec<String> = match &last {
+                Some(last) => current
+                    .iter()
+                    .filter(|entry| !last.contains(entry))
+                    .map(|(path, _)| path.display().to_string())
+                    .collect(),
+                None => vec![],
+            };
+
+            print!("{}", ANSI_CLEAR);
+            println!("{}Day {:02}{}", ANSI_BOLD, day, ANSI_RESET);
+            if !changed.is_empty() {
+                println!("Changed: {}", changed.join(", "));
+            }
+            println!("---");
+
+            if run_tests(day, release) {
+                println!("---");
+                run_solution(day, release);
+            }
+
+            println!("---");
+            println!("Watching for changes, press Ctrl+C to stop.");
+
+            // files written while running are picked up on the next poll.
+            last = Some(current);
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::watched_paths;
+    use crate::template::input;
+
+    #[test]
+    fn watches_sources_but_not_other_days() {
+        let paths = watched_paths(1);
+        let crate_dir = input::get_crate_dir();
+        let watches = |path: &str| paths.iter().any(|p| *p == crate_dir.join(path));
+
+        assert!(watches("build.rs"));
+        assert!(watches("Cargo.toml"));
+        assert!(watches("src/bin/01.rs"));
+        assert!(watches("src/lib.rs"));
+        assert!(watches("src/template/runner.rs"));
+        assert!(!watches("src/bin/02.rs"));
+    }
+}
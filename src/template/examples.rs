@@ -0,0 +1,376 @@
+/// Examples from the puzzle description. `extract` pulls the example input out of `data/puzzles/DD.md` into
+/// `data/examples/DD.txt` and the emphasised example answers into `data/examples/DD.toml`, which tests check against.
+///
+/// `DD.toml` is a manifest: the root table describes `DD.txt`, every other section a named example read from
+/// `data/examples/DD/<name>.txt` (or the file in its `input` key, relative to `data/examples`).
+/// `[params]` and `[<name>.params]` hold puzzle parameters for an example:
+///
+/// ```toml
+/// part_one = 13
+/// part_two = 1
+///
+/// [larger]
+/// part_two = 36
+///
+/// [larger.params]
+/// knots = 10
+/// ```
+///
+/// `build.rs` expands the manifest into one test per example and part, see `example_tests!`.
+use std::{fmt::Display, fs, io, path::PathBuf};
+
+use crate::template::{
+    answers::{self, Answers},
+    input, read_file, runner, toml,
+};
+
+/// Name of the example in `DD.txt`.
+pub const DEFAULT_EXAMPLE: &str = "example";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Extracted {
+    /// Contents of every code block in the description, in order.
+    pub blocks: Vec<String>,
+    /// Index of the block that most likely is the example input.
+    pub proposed: Option<usize>,
+    pub answers: Answers,
+}
+
+/// Parse a puzzle description: the example input is the first code block introduced by a paragraph mentioning an
+/// example (falling back to the longest block of part one), the answers are the last emphasised code of each part.
+pub fn extract(markdown: &str) -> Extracted {
+    let (part_one, part_two) = match markdown.find("--- Part Two ---") {
+        Some(index) => (&markdown[..index], Some(&markdown[index..])),
+        None => (markdown, None),
+    };
+
+    let mut blocks = vec![];
+    let mut part_one_blocks = 0;
+    let mut proposed = None;
+
+    for (section, is_part_one) in [(part_one, true), (part_two.unwrap_or_default(), false)] {
+        let mut text = String::new();
+        let mut block: Option<String> = None;
+
+        for line in section.lines() {
+            match (&mut block, line.starts_with("```")) {
+                (None, true) => block = Some(String::new()),
+                (Some(_), true) => {
+                    if is_part_one && proposed.is_none() && introduces_example(&text) {
+                        proposed = Some(blocks.len());
+                    }
+                    blocks.push(block.take().unwrap());
+                    text.clear();
+                }
+                (Some(block), false) => {
+                    block.push_str(line);
+                    block.push('\n');
+                }
+                (None, false) => {
+                    text.push_str(line);
+                    text.push('\n');
+                }
+            }
+        }
+
+        if is_part_one {
+            part_one_blocks = blocks.len();
+        }
+    }
+
+    let proposed = proposed
+        .or_else(|| (0..part_one_blocks).max_by_key(|&i| (blocks[i].len(), std::cmp::Reverse(i))));
+
+    Extracted {
+        blocks,
+        proposed,
+        answers: Answers {
+            part_one: last_emphasised_code(part_one),
+            part_two: part_two.and_then(last_emphasised_code),
+        },
+    }
+}
+
+/// Whether a paragraph leads into an example. Some descriptions in `data/puzzles` are translated.
+fn introduces_example(text: &str) -> bool {
+    let text = text.to_lowercase();
+    ["example", "例如", "比如"]
+        .iter()
+        .any(|word| text.contains(word))
+}
+
+/// Find the last `` `*x*` `` or ``*`x`*`` in a section.
+fn last_emphasised_code(section: &str) -> Option<String> {
+    ["`*", "*`"]
+        .iter()
+        .filter_map(|open| {
+            let close: String = open.chars().rev().collect();
+            let start = section.rfind(open)? + open.len();
+            let end = section[start..].find(&close)? + start;
+            Some((start, section[start..end].to_string()))
+        })
+        .filter(|(_, value)| !value.is_empty() && !value.contains('\n'))
+        .max_by_key(|(start, _)| *start)
+        .map(|(_, value)| value)
+}
+
+pub fn get_example_path(day: u8) -> PathBuf {
+    input::get_data_path("examples", day)
+}
+
+pub fn get_example_answers_path(day: u8) -> PathBuf {
+    input::get_day_path("examples", day, "toml")
+}
+
+/// Read the expected answers for a day's example. A missing file means no answers are known yet.
+pub fn read_answers(day: u8) -> Result<Answers, answers::Error> {
+    match fs::read_to_string(get_example_answers_path(day)) {
+        Ok(s) => Answers::from_str(&s),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Replace the answers in the root table of a manifest, keeping its other keys, `[params]` and named examples.
+fn merge_answers(manifest: &str, answers: &Answers) -> String {
+    let mut root = answers.to_toml();
+    let mut sections = String::new();
+
+    for line in manifest.lines() {
+        if !sections.is_empty() || line.trim_start().starts_with('[') {
+            sections.push_str(line);
+            sections.push('\n');
+            continue;
+        }
+
+        let key = line.split('=').next().unwrap_or_default().trim();
+        if key != "part_one" && key != "part_two" {
+            root.push_str(line);
+            root.push('\n');
+        }
+    }
+
+    if !sections.is_empty() && !root.trim().is_empty() && !root.ends_with("\n\n") {
+        root.push('\n');
+    }
+
+    format!("{}{}", root.trim_start(), sections)
+}
+
+/// Store the answers of the default example. The rest of an existing manifest is kept.
+pub fn write_answers(day: u8, answers: &Answers) -> Result<(), io::Error> {
+    let path = get_example_answers_path(day);
+    let manifest = match fs::read_to_string(&path) {
+        Ok(s) => s,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+
+    fs::write(path, merge_answers(&manifest, answers))
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Example {
+    pub name: String,
+    /// Path of the input, inside the data directory.
+    pub input_path: PathBuf,
+    pub answers: Answers,
+    pub params: Vec<(String, String)>,
+}
+
+impl Example {
+    pub fn read_input(&self) -> Result<String, io::Error> {
+        fs::read_to_string(&self.input_path).map(|s| s.replace("\r\n", "\n"))
+    }
+}
+
+fn parse_manifest(day: u8, s: &str) -> Result<Vec<Example>, answers::Error> {
+    let doc = toml::parse(s).map_err(answers::Error::Parser)?;
+    let params = |name: &str| {
+        doc.table(name)
+            .map(|table| table.entries.clone())
+            .unwrap_or_default()
+    };
+
+    let mut examples = vec![Example {
+        name: DEFAULT_EXAMPLE.to_string(),
+        input_path: get_example_path(day),
+        answers: doc.root().map(Answers::from_table).unwrap_or_default(),
+        params: params("params"),
+    }];
+
+    for section in doc
+        .sections()
+        .filter(|s| s.name != "params" && !s.name.contains('.'))
+    {
+        let input_path = match section.get("input") {
+            Some(input) => input::get_data_dir().join("examples").join(input),
+            None => input::get_data_dir()
+                .join("examples")
+                .join(format!("{:02}", day))
+                .join(format!("{}.txt", section.name)),
+        };
+
+        examples.push(Example {
+            name: section.name.clone(),
+            input_path,
+            answers: Answers::from_table(section),
+            params: params(&format!("{}.params", section.name)),
+        });
+    }
+
+    Ok(examples)
+}
+
+/// Read the example manifest of a day. Without a manifest, the day has just the default example without answers.
+pub fn read_manifest(day: u8) -> Result<Vec<Example>, answers::Error> {
+    match fs::read_to_string(get_example_answers_path(day)) {
+        Ok(s) => parse_manifest(day, &s),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => parse_manifest(day, ""),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Run one part on a named example and assert that the result matches the answer in the manifest.
+/// Called by the tests generated through `example_tests!`.
+pub fn check<T: Display>(
+    day: u8,
+    name: &str,
+    part: u8,
+    solve: impl Fn(&str, &Example) -> Option<T>,
+) {
+    let examples = read_manifest(day).unwrap_or_else(|e| panic!("{}", e));
+    let example = examples
+        .iter()
+        .find(|e| e.name == name)
+        .unwrap_or_else(|| panic!("no example \"{}\" for day {}.", name, day));
+
+    let input = example
+        .read_input()
+        .unwrap_or_else(|e| panic!("could not read \"{}\": {}", example.input_path.display(), e));
+
+    let result = solve(&input, example).map(|r| r.to_string());
+    assert_eq!(result.as_deref(), example.answers.get(part));
+}
+
+/// Run one part on the real input and assert that the result matches the answer in `data/answers/DD.toml`.
+/// Called by the tests generated through `tests!`. Skipped when `AOC_DATA_DIR` replaces the data directory.
+pub fn check_real_input<T: Display>(day: u8, part: u8, solve: impl Fn(&str) -> Option<T>) {
+    if !input::is_default_data_dir() {
+        eprintln!("AOC_DATA_DIR is set, not checking against stored answers.");
+        return;
+    }
+
+    let answers = answers::read(day).unwrap_or_else(|e| panic!("{}", e));
+    let input = read_file("inputs", day).unwrap_or_else(|e| panic!("{}", e));
+
+    let result = solve(&input).map(|r| runner::to_answer(&r));
+    assert_eq!(result.as_deref(), answers.get(part));
+}
+
+/// Picks one answer of a combined `solve`, so that example tests of days using `main!(day, solve)` can refer to
+/// `part_one` and `part_two` like those of other days.
+pub trait Part {
+    fn select<A: Display, B: Display>(answers: (Option<A>, Option<B>)) -> Option<String>;
+}
+
+pub struct PartOne;
+pub struct PartTwo;
+
+impl Part for PartOne {
+    fn select<A: Display, B: Display>(answers: (Option<A>, Option<B>)) -> Option<String> {
+        answers.0.map(|a| a.to_string())
+    }
+}
+
+impl Part for PartTwo {
+    fn select<A: Display, B: Display>(answers: (Option<A>, Option<B>)) -> Option<String> {
+        answers.1.map(|b| b.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{extract, merge_answers, parse_manifest, DEFAULT_EXAMPLE};
+    use crate::template::answers::Answers;
+    use std::fs;
+
+    #[test]
+    fn extracts_examples_from_puzzles() {
+        // day 9 combines both examples and day 19 joins each blueprint onto one line.
+        for day in (1..=21).filter(|day| ![9, 19].contains(day)) {
+            let markdown = fs::read_to_string(format!("data/puzzles/{:02}.md", day)).unwrap();
+            let example = fs::read_to_string(format!("data/examples/{:02}.txt", day)).unwrap();
+
+            let extracted = extract(&markdown);
+            let proposed = &extracted.blocks[extracted.proposed.unwrap()];
+
+            let lines = |s: &str| -> Vec<String> {
+                s.trim_end()
+                    .lines()
+                    .map(|l| l.trim_end().to_string())
+                    .collect()
+            };
+            assert_eq!(lines(proposed), lines(&example), "day {}", day);
+        }
+    }
+
+    #[test]
+    fn extracts_emphasised_answers() {
+        let markdown = fs::read_to_string("data/puzzles/01.md").unwrap();
+        let answers = extract(&markdown).answers;
+        assert_eq!(answers.part_one.as_deref(), Some("24000"));
+        assert_eq!(answers.part_two.as_deref(), Some("45000"));
+
+        let markdown = fs::read_to_string("data/puzzles/05.md").unwrap();
+        let answers = extract(&markdown).answers;
+        assert_eq!(answers.part_one.as_deref(), Some("CMZ"));
+        assert_eq!(answers.part_two.as_deref(), Some("MCD"));
+    }
+
+    #[test]
+    fn parses_manifests() {
+        let manifest = "part_one = 13\n\n[params]\nrow = 10\n\n[larger]\npart_two = 36\n\n[larger.params]\nrow = 20\n\n[other]\ninput = \"09.txt\"\n";
+        let examples = parse_manifest(9, manifest).unwrap();
+
+        let names: Vec<&str> = examples.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec![DEFAULT_EXAMPLE, "larger", "other"]);
+
+        assert!(examples[0].input_path.ends_with("examples/09.txt"));
+        assert_eq!(examples[0].answers.part_one.as_deref(), Some("13"));
+        assert_eq!(examples[0].params, vec![("row".into(), "10".into())]);
+
+        assert!(examples[1].input_path.ends_with("examples/09/larger.txt"));
+        assert_eq!(examples[1].answers.get(1), None);
+        assert_eq!(examples[1].answers.get(2), Some("36"));
+        assert_eq!(examples[1].params, vec![("row".into(), "20".into())]);
+
+        assert!(examples[2].input_path.ends_with("examples/09.txt"));
+    }
+
+    #[test]
+    fn keeps_sections_when_writing_answers() {
+        let answers = Answers {
+            part_one: Some("24".into()),
+            part_two: None,
+        };
+
+        let manifest =
+            "part_one = 13\npart_two = 1\n\n[params]\nrow = 10\n\n[larger]\npart_two = 36\n";
+        let merged = merge_answers(manifest, &answers);
+        assert_eq!(
+            merged,
+            "part_one = 24\n\n[params]\nrow = 10\n\n[larger]\npart_two = 36\n"
+        );
+
+        let examples = parse_manifest(9, &merged).unwrap();
+        assert_eq!(examples[0].params, vec![("row".into(), "10".into())]);
+        assert_eq!(examples[1].answers.get(2), Some("36"));
+
+        assert_eq!(merge_answers("", &answers), "part_one = 24\n");
+        assert_eq!(
+            merge_answers("[params]\nrow = 10\n", &answers),
+            "part_one = 24\n\n[params]\nrow = 10\n"
+        );
+    }
+}
@@ -0,0 +1,177 @@
+/// Loading puzzle inputs. Files are resolved against the data directory: `AOC_DATA_DIR` if set, otherwise the
+/// `data` folder of the crate root, found by walking up from the working directory. A day's binary reads its input
+/// from stdin when passed `-` (or `--input -`), and from an arbitrary file when passed `--input <path>`.
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process,
+};
+
+#[derive(Debug)]
+pub enum Error {
+    IO(PathBuf, io::Error),
+    Stdin(io::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::IO(path, e) => {
+                write!(f, "could not read input file \"{}\": {}", path.display(), e)
+            }
+            Error::Stdin(e) => write!(f, "could not read input from stdin: {}", e),
+        }
+    }
+}
+
+/// Where a day's input comes from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    Data,
+    Stdin,
+    Path(PathBuf),
+}
+
+/// The nearest ancestor of the working directory with a `Cargo.toml`. Falls back to the working directory.
+pub fn get_crate_dir() -> PathBuf {
+    let cwd = env::current_dir().unwrap_or_default();
+    cwd.ancestors()
+        .find(|dir| dir.join("Cargo.toml").is_file())
+        .unwrap_or(&cwd)
+        .to_path_buf()
+}
+
+/// `data` in the crate directory.
+fn default_data_dir() -> PathBuf {
+    get_crate_dir().join("data")
+}
+
+/// The data directory: `AOC_DATA_DIR`, or the crate's `data` folder.
+pub fn get_data_dir() -> PathBuf {
+    match env::var_os("AOC_DATA_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => default_data_dir(),
+    }
+}
+
+/// Whether `AOC_DATA_DIR` points somewhere other than the crate's `data` folder.
+pub fn is_default_data_dir() -> bool {
+    let data_dir = get_data_dir();
+    let default = default_data_dir();
+    data_dir == default
+        || matches!(
+            (data_dir.canonicalize(), default.canonicalize()),
+            (Ok(a), Ok(b)) if a == b
+        )
+}
+
+/// `<data dir>/<folder>/DD.<extension>`.
+pub fn get_day_path(folder: &str, day: u8, extension: &str) -> PathBuf {
+    get_data_dir()
+        .join(folder)
+        .join(format!("{:02}.{}", day, extension))
+}
+
+pub fn get_data_path(folder: &str, day: u8) -> PathBuf {
+    get_day_path(folder, day, "txt")
+}
+
+pub fn read_path(path: &Path) -> Result<String, Error> {
+    fs::read_to_string(path)
+        .map(|s| s.replace("\r\n", "\n"))
+        .map_err(|e| Error::IO(path.to_path_buf(), e))
+}
+
+/// Read `<data dir>/<folder>/DD.txt`.
+pub fn read_file(folder: &str, day: u8) -> Result<String, Error> {
+    read_path(&get_data_path(folder, day))
+}
+
+fn parse_source(args: &[String]) -> Result<Source, String> {
+    if let Some(index) = args.iter().position(|arg| arg == "--input") {
+        return match args.get(index + 1).map(String::as_str) {
+            Some("-") => Ok(Source::Stdin),
+            Some(path) => Ok(Source::Path(PathBuf::from(path))),
+            None => Err(
+                "Unexpected command-line input. Format: cargo solve 1 --input <path>".to_string(),
+            ),
+        };
+    }
+
+    if args.iter().skip(1).any(|arg| arg == "-") {
+        return Ok(Source::Stdin);
+    }
+
+    Ok(Source::Data)
+}
+
+/// The input source selected on the command line.
+pub fn get_source() -> Source {
+    let args: Vec<String> = env::args().collect();
+    match parse_source(&args) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Whether the input was replaced on the command line or through `AOC_DATA_DIR`, in which case stored answers do
+/// not apply.
+pub fn is_overridden() -> bool {
+    get_source() != Source::Data || !is_default_data_dir()
+}
+
+pub fn read_input(day: u8) -> Result<String, Error> {
+    match get_source() {
+        Source::Data => read_file("inputs", day),
+        Source::Path(path) => read_path(&path),
+        Source::Stdin => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(Error::Stdin)?;
+            Ok(input.replace("\r\n", "\n"))
+        }
+    }
+}
+
+/// Read the day's input from the selected source. Exits with the path that was tried if that fails.
+pub fn from_args(day: u8) -> String {
+    match read_input(day) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_source, Source};
+    use std::path::PathBuf;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_input_sources() {
+        assert_eq!(parse_source(&args(&["01", "--time"])), Ok(Source::Data));
+        assert_eq!(parse_source(&args(&["01", "-"])), Ok(Source::Stdin));
+        assert_eq!(
+            parse_source(&args(&["01", "--input", "-"])),
+            Ok(Source::Stdin)
+        );
+        assert_eq!(
+            parse_source(&args(&["01", "--input", "other/01.txt"])),
+            Ok(Source::Path(PathBuf::from("other/01.txt")))
+        );
+        assert!(parse_source(&args(&["01", "--input"])).is_err());
+    }
+}
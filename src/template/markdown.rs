@@ -0,0 +1,220 @@
+/// Convert the HTML of a puzzle page into the markdown stored in `data/puzzles`.
+/// Only the contents of `<main>` are kept, and only the handful of elements used on adventofcode.com are handled.
+pub fn from_html(html: &str) -> String {
+    let main = match (html.find("<main>"), html.rfind("</main>")) {
+        (Some(start), Some(end)) if start < end => &html[start + "<main>".len()..end],
+        _ => html,
+    };
+
+    let mut converter = Converter::default();
+
+    let mut rest = main;
+    while let Some(start) = rest.find('<') {
+        converter.text(&rest[..start]);
+
+        let end = match rest[start..].find('>') {
+            Some(end) => start + end,
+            None => break,
+        };
+
+        converter.tag(&rest[start + 1..end]);
+        rest = &rest[end + 1..];
+    }
+    converter.text(rest);
+
+    let mut out = converter.out.trim_end().to_string();
+    out.push('\n');
+    out
+}
+
+#[derive(Default)]
+struct Converter {
+    out: String,
+    in_pre: bool,
+    skip_depth: usize,
+    link_targets: Vec<String>,
+    heading_start: Option<usize>,
+}
+
+impl Converter {
+    fn text(&mut self, text: &str) {
+        if self.skip_depth > 0 || text.is_empty() {
+            return;
+        }
+
+        let text = decode_entities(text);
+
+        if self.in_pre {
+            self.out.push_str(&text);
+            return;
+        }
+
+        // collapse whitespace like a browser would, without leading spaces at the start of a line.
+        let mut last_was_space = self.out.is_empty() || self.out.ends_with([' ', '\n']);
+        for c in text.chars() {
+            if c.is_whitespace() {
+                if !last_was_space {
+                    self.out.push(' ');
+                }
+                last_was_space = true;
+            } else {
+                self.out.push(c);
+                last_was_space = false;
+            }
+        }
+    }
+
+    fn end_block(&mut self) {
+        let trimmed = self.out.trim_end_matches(' ').len();
+        self.out.truncate(trimmed);
+
+        if !self.out.is_empty() && !self.out.ends_with("\n\n") {
+            self.out.push_str(if self.out.ends_with('\n') {
+                "\n"
+            } else {
+                "\n\n"
+            });
+        }
+    }
+
+    fn tag(&mut self, tag: &str) {
+        let is_closing = tag.starts_with('/');
+        let tag = tag.trim_start_matches('/').trim_end_matches('/');
+        let (name, attributes) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+        let name = name.to_ascii_lowercase();
+
+        if matches!(name.as_str(), "script" | "style" | "form") {
+            if is_closing {
+                self.skip_depth = self.skip_depth.saturating_sub(1);
+            } else {
+                self.skip_depth += 1;
+            }
+            return;
+        }
+
+        if self.skip_depth > 0 {
+            return;
+        }
+
+        match (name.as_str(), is_closing) {
+            ("p" | "article" | "ul" | "div", _) => self.end_block(),
+            ("h2", false) => {
+                self.end_block();
+                self.heading_start = Some(self.out.len());
+            }
+            ("h2", true) => {
+                if let Some(start) = self.heading_start.take() {
+                    // escape the leading dashes, otherwise they would read as a horizontal rule.
+                    if self.out[start..].starts_with('-') {
+                        self.out.insert(start, '\\');
+                    }
+                }
+                self.out.push_str("\n----------\n\n");
+            }
+            ("pre", false) => {
+                self.end_block();
+                self.out.push_str("```\n");
+                self.in_pre = true;
+            }
+            ("pre", true) => {
+                if !self.out.ends_with('\n') {
+                    self.out.push('\n');
+                }
+                self.out.push_str("```\n\n");
+                self.in_pre = false;
+            }
+            ("li", false) => self.out.push_str("* "),
+            ("li", true) => {
+                let trimmed = self.out.trim_end_matches(' ').len();
+                self.out.truncate(trimmed);
+                self.out.push('\n');
+            }
+            ("em" | "strong" | "b" | "i", _) if !self.in_pre => self.out.push('*'),
+            ("code", _) if !self.in_pre => self.out.push('`'),
+            ("br", _) => self.out.push('\n'),
+            ("a", false) => {
+                self.link_targets
+                    .push(get_attribute(attributes, "href").unwrap_or_default());
+                self.out.push('[');
+            }
+            ("a", true) => {
+                let target = self.link_targets.pop().unwrap_or_default();
+                self.out.push_str(&format!("]({})", target));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn get_attribute(attributes: &str, name: &str) -> Option<String> {
+    let (_, rest) = attributes.split_once(&format!("{}=", name))?;
+    let quote = rest.chars().next()?;
+    if quote != '"' && quote != '\'' {
+        return rest.split_whitespace().next().map(decode_entities);
+    }
+    let (value, _) = rest[1..].split_once(quote)?;
+    Some(decode_entities(value))
+}
+
+fn decode_entities(s: &str) -> String {
+    if !s.contains('&') {
+        return s.to_string();
+    }
+
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::from_html;
+
+    #[test]
+    fn converts_puzzle_description() {
+        let html = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Calorie Counting ---</h2><p>Santa's reindeer need <a href="/2018/day/25">magical energy</a>, a <em>star</em>.</p>
+<pre><code>1000
+2000
+
+3000
+</code></pre>
+<ul><li>A total of <code><em>6000</em></code> Calories.</li><li>this is <em><code>24000</code></em> &lt;here&gt;.</li></ul>
+<pre><code>a<em>b</em></code></pre>
+</article>
+<p>Your puzzle answer was <code>72511</code>.</p>
+<script>alert("nope")</script>
+</main></body></html>"#;
+
+        let expected = [
+            "\\--- Day 1: Calorie Counting ---",
+            "----------",
+            "",
+            "Santa's reindeer need [magical energy](/2018/day/25), a *star*.",
+            "",
+            "```",
+            "1000",
+            "2000",
+            "",
+            "3000",
+            "```",
+            "",
+            "* A total of `*6000*` Calories.",
+            "* this is *`24000`* <here>.",
+            "",
+            "```",
+            "ab",
+            "```",
+            "",
+            "Your puzzle answer was `72511`.",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(from_html(html), expected);
+    }
+}
@@ -1,35 +1,359 @@
-use std::{env, fs};
-
-pub mod aoc_cli;
+pub mod alloc;
+pub mod answers;
+pub mod aoc_client;
+pub mod bench_history;
 pub mod commands;
+pub mod examples;
+pub mod input;
+pub mod markdown;
+pub mod params;
+pub mod properties;
 pub mod readme_benchmarks;
+pub mod readme_progress;
+pub mod robustness;
 pub mod runner;
+pub mod selection;
+pub mod spans;
+pub mod submissions;
+pub mod toml;
+#[cfg(feature = "viz")]
+pub mod viz;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
-pub fn read_file(folder: &str, day: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{:02}.txt", day));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file").replace("\r\n", "\n")
-}
+pub use input::read_file;
 
 /// main! produces a block setting up the input and runner for each part.
+/// `parse` returns either `Input` or `Result<Input, ParseError>`, whose errors are printed as a diagnostic.
+/// `main!(day, params: { name: Type = default, ... })` also declares the day's `Params`, which are passed to each part
+/// as a second argument, see `template::params`.
+/// `main!(day, solve)` runs `fn solve(input: Input) -> (Option<A>, Option<B>)` instead of the two parts, for solutions
+/// whose second part reuses the work of the first. Its example tests are the ones listed in the manifest.
 #[macro_export]
 macro_rules! main {
     ($day:expr) => {
+        advent_of_code::main!(@allocator);
+        #[allow(unused_imports)]
+        use advent_of_code::template::runner::no_variants::*;
+
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", $day);
-            let parsed = run_parser(parse, &input);
+            if advent_of_code::template::robustness::is_requested() {
+                advent_of_code::template::robustness::run($day, parse);
+                return;
+            }
+            // leaked so that parts may borrow from it while running on a separate thread for `--timeout`.
+            let input: &'static str =
+                Box::leak(advent_of_code::template::input::from_args($day).into_boxed_str());
+            if is_variants_run() {
+                __run_variants(input, $day);
+                return;
+            }
+            let parsed: Input = run_parser(parse, input);
             run_part(part_one, parsed.clone(), $day, 1);
             run_part(part_two, parsed.clone(), $day, 2);
+            print_peak_memory();
+        }
+
+        /// Used by the tests generated by `example_tests!`.
+        #[cfg(test)]
+        #[allow(dead_code)]
+        fn __run_example<I, T>(part: impl Fn(I) -> T, input: I, params: &[(String, String)]) -> T {
+            assert!(params.is_empty(), "day {} takes no params.", $day);
+            part(input)
+        }
+    };
+    ($day:expr, solve) => {
+        advent_of_code::main!(@allocator);
+
+        fn main() {
+            use advent_of_code::template::runner::*;
+            if advent_of_code::template::robustness::is_requested() {
+                advent_of_code::template::robustness::run($day, parse);
+                return;
+            }
+            let input: &'static str =
+                Box::leak(advent_of_code::template::input::from_args($day).into_boxed_str());
+            if is_variants_run() {
+                no_variants::__run_variants(input, $day);
+                return;
+            }
+            let parsed: Input = run_parser(parse, input);
+            run_combined(solve, parsed, $day);
+            print_peak_memory();
+        }
+
+        /// Stands in for the part in the tests generated by `example_tests!`.
+        #[cfg(test)]
+        #[allow(dead_code)]
+        fn part_one(
+            part: advent_of_code::template::examples::PartOne,
+        ) -> advent_of_code::template::examples::PartOne {
+            part
+        }
+
+        #[cfg(test)]
+        #[allow(dead_code)]
+        fn part_two(
+            part: advent_of_code::template::examples::PartTwo,
+        ) -> advent_of_code::template::examples::PartTwo {
+            part
+        }
+
+        /// Used by the tests generated by `example_tests!`.
+        #[cfg(test)]
+        #[allow(dead_code)]
+        fn __run_example<P: advent_of_code::template::examples::Part>(
+            _part: fn(P) -> P,
+            input: Input,
+            params: &[(String, String)],
+        ) -> Option<String> {
+            assert!(params.is_empty(), "day {} takes no params.", $day);
+            P::select(solve(input))
+        }
+    };
+    ($day:expr, params: { $($name:ident: $type:ty = $default:expr),* $(,)? }) => {
+        advent_of_code::main!(@allocator);
+
+        #[derive(Clone, Debug)]
+        pub struct Params {
+            $(pub $name: $type,)*
+        }
+
+        impl Default for Params {
+            fn default() -> Self {
+                Params {
+                    $($name: $default,)*
+                }
+            }
+        }
+
+        impl advent_of_code::template::params::Params for Params {
+            fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+                match key {
+                    $(stringify!($name) => {
+                        self.$name = value.parse().map_err(|_| {
+                            format!("invalid value \"{}\" for param \"{}\".", value, key)
+                        })?
+                    })*
+                    _ => return Err(format!("unknown param \"{}\" for day {}.", key, $day)),
+                }
+                Ok(())
+            }
+        }
+
+        fn main() {
+            use advent_of_code::template::runner::*;
+            let params: Params = advent_of_code::template::params::from_args();
+            if advent_of_code::template::robustness::is_requested() {
+                advent_of_code::template::robustness::run($day, parse);
+                return;
+            }
+            // leaked so that parts may borrow from it while running on a separate thread for `--timeout`.
+            let input: &'static str =
+                Box::leak(advent_of_code::template::input::from_args($day).into_boxed_str());
+            if is_variants_run() {
+                no_variants::__run_variants(input, $day);
+                return;
+            }
+            let parsed: Input = run_parser(parse, input);
+            let params_one = params.clone();
+            run_part(move |input| part_one(input, &params_one), parsed.clone(), $day, 1);
+            run_part(move |input| part_two(input, &params), parsed.clone(), $day, 2);
+            print_peak_memory();
+        }
+
+        /// Used by the tests generated by `example_tests!`.
+        #[cfg(test)]
+        #[allow(dead_code)]
+        fn __run_example<I, T>(
+            part: impl Fn(I, &Params) -> T,
+            input: I,
+            params: &[(String, String)],
+        ) -> T {
+            let params: Params = advent_of_code::template::params::from_entries(params)
+                .unwrap_or_else(|e| panic!("{}", e));
+            part(input, &params)
+        }
+    };
+    (@allocator) => {
+        #[cfg(feature = "alloc-profile")]
+        #[global_allocator]
+        static ALLOCATOR: advent_of_code::template::alloc::CountingAllocator =
+            advent_of_code::template::alloc::CountingAllocator;
+    };
+}
+
+/// variants! registers alternative implementations of the parts of a `main!(day)` solution, e.g. a naive version
+/// next to an optimised one. `cargo solve DD --variants` runs each part and its variants on the same parsed input,
+/// checks that they agree and compares their timings. The `solve` and `params` forms of `main!` do not support
+/// variants.
+///
+/// ```ignore
+/// advent_of_code::variants! {
+///     part_one: [part_one_vec],
+///     part_two: [part_two_vec],
+/// }
+/// ```
+#[macro_export]
+macro_rules! variants {
+    ($($part:ident: [$($variant:ident),* $(,)?]),* $(,)?) => {
+        /// Shadows the fallback imported by `main!`.
+        fn __run_variants(input: &'static str, day: u8) {
+            use advent_of_code::template::runner::*;
+            let parsed: Input = run_parser(parse, input);
+            println!();
+            let mut agree = true;
+            $(
+                agree &= run_variants(
+                    stringify!($part),
+                    parsed.clone(),
+                    &[
+                        (stringify!($part), $part as fn(_) -> _),
+                        $((stringify!($variant), $variant as fn(_) -> _),)*
+                    ],
+                );
+            )*
+            finish_variants(day, agree);
+        }
+    };
+}
+
+/// span! times the rest of the enclosing block as a named sub-phase of a part, see `template::spans`.
+#[macro_export]
+macro_rules! span {
+    ($name:expr) => {
+        advent_of_code::template::spans::enter($name)
+    };
+}
+
+/// viz! records a frame of a simulation, see `template::viz`. The frame is only built while recording with `--viz`,
+/// and the macro expands to nothing without the `viz` feature.
+///
+/// ```ignore
+/// advent_of_code::viz!(Frame::from_grid(&grid, PALETTE, |cell| *cell as u8));
+/// ```
+#[macro_export]
+macro_rules! viz {
+    ($frame:expr) => {
+        #[cfg(feature = "viz")]
+        advent_of_code::template::viz::frame(|| $frame);
+    };
+}
+
+/// tests! produces the `tests` module of a day: one test per part listed with its expected result on the example,
+/// the tests of the example manifest (`example_tests!`), and tests against the real input for every answer stored in
+/// `data/answers/DD.toml`. The latter are ignored by default, run them with `cargo test -- --ignored`.
+///
+/// ```ignore
+/// advent_of_code::tests! {
+///     day: 9,
+///     part_one: Some(13),
+///     #[ignore = "slow in debug builds"]
+///     part_two("09/larger.txt"): Some(36),
+/// }
+/// ```
+///
+/// A file name after the part replaces the example input, relative to `data/examples`.
+#[macro_export]
+macro_rules! tests {
+    (day: $day:literal $(, $(#[$attr:meta])* $part:ident $(($input:literal))?: $expected:expr)* $(,)?) => {
+        #[cfg(test)]
+        mod tests {
+            use super::*;
+
+            $(
+                mod $part {
+                    use super::*;
+
+                    #[test]
+                    $(#[$attr])*
+                    fn example() {
+                        use advent_of_code::template::input;
+
+                        #[allow(unused_mut, unused_assignments)]
+                        let mut path = input::get_data_path("examples", $day);
+                        $(path = input::get_data_dir().join("examples").join($input);)?
+
+                        let input = input::read_path(&path).unwrap_or_else(|e| panic!("{}", e));
+                        assert_eq!(__run_example($part, advent_of_code::parse::unwrap(parse(&input)), &[]), $expected);
+                    }
+                }
+            )*
+
+            advent_of_code::example_tests!($day);
+            include!(concat!(env!("OUT_DIR"), "/inputs_", $day, ".rs"));
         }
     };
 }
+
+/// reference_tests! compares the day's parts with slow, obviously correct reference solutions on random inputs, see
+/// `template::properties`. `generate` builds a puzzle input from a seeded `StdRng`, and each listed part is checked
+/// against its reference, which takes the same arguments. Days with params pass them like an example manifest.
+///
+/// ```ignore
+/// advent_of_code::reference_tests! {
+///     generate: reference::generate,
+///     params: { row: 10 },
+///     part_one: reference::part_one,
+/// }
+/// ```
+#[macro_export]
+macro_rules! reference_tests {
+    (
+        generate: $generate:path,
+        params: { $($key:ident: $value:expr),* $(,)? },
+        $($part:ident: $reference:path),* $(,)?
+    ) => {
+        #[cfg(test)]
+        mod reference_tests {
+            use super::*;
+
+            fn params() -> Vec<(String, String)> {
+                #[allow(unused_mut)]
+                let mut params: Vec<(String, String)> = vec![];
+                $(params.push((stringify!($key).to_string(), $value.to_string()));)*
+                params
+            }
+
+            $(
+                mod $part {
+                    use super::*;
+
+                    #[test]
+                    fn reference() {
+                        let params = params();
+                        advent_of_code::template::properties::compare(
+                            $generate,
+                            |input: &String| {
+                                __run_example($part, advent_of_code::parse::unwrap(parse(input)), &params)
+                            },
+                            |input: &String| {
+                                __run_example($reference, advent_of_code::parse::unwrap(parse(input)), &params)
+                            },
+                        );
+                    }
+                }
+            )*
+        }
+    };
+    (generate: $generate:path, $($part:ident: $reference:path),* $(,)?) => {
+        advent_of_code::reference_tests! {
+            generate: $generate,
+            params: {},
+            $($part: $reference),*
+        }
+    };
+}
+
+/// example_tests! expands the day's example manifest (`data/examples/DD.toml`) into one test per example and part.
+/// The tests are generated by `build.rs` and call `part_one` / `part_two` and `parse` from the surrounding scope,
+/// passing the example's `[params]` to days that declare them.
+#[macro_export]
+macro_rules! example_tests {
+    ($day:literal) => {
+        include!(concat!(env!("OUT_DIR"), "/examples_", $day, ".rs"));
+    };
+}
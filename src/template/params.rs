@@ -0,0 +1,77 @@
+/// Puzzle parameters. `main!(15, params: { row: isize = 2_000_000 })` declares a `Params` struct for the day whose
+/// defaults apply to the real input. Example manifests override them in their `[params]` tables, and
+/// `--param row=10` overrides them on the command line.
+use std::{env, process};
+
+pub trait Params: Default {
+    /// Parse and assign one parameter by name.
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String>;
+}
+
+/// Build parameters from their defaults and a list of overrides.
+pub fn from_entries<P: Params>(entries: &[(String, String)]) -> Result<P, String> {
+    let mut params = P::default();
+    for (key, value) in entries {
+        params.set(key, value)?;
+    }
+    Ok(params)
+}
+
+/// The `--param key=value` overrides passed on the command line.
+pub fn get_overrides() -> Vec<(String, String)> {
+    let args: Vec<String> = env::args().collect();
+    let mut overrides = vec![];
+
+    for (i, _) in args.iter().enumerate().filter(|(_, arg)| *arg == "--param") {
+        match args.get(i + 1).and_then(|p| p.split_once('=')) {
+            Some((key, value)) => {
+                overrides.push((key.trim().to_string(), value.trim().to_string()))
+            }
+            None => {
+                eprintln!("Unexpected command-line input. Format: cargo solve 15 --param row=10");
+                process::exit(1);
+            }
+        }
+    }
+
+    overrides
+}
+
+/// Build parameters from their defaults and the command line. Exits on unknown or malformed parameters.
+pub fn from_args<P: Params>() -> P {
+    match from_entries(&get_overrides()) {
+        Ok(params) => params,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{from_entries, Params};
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Mock {
+        row: isize,
+    }
+
+    impl Params for Mock {
+        fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+            match key {
+                "row" => self.row = value.parse().map_err(|_| "invalid row".to_string())?,
+                _ => return Err(format!("unknown param \"{}\".", key)),
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn applies_overrides() {
+        let params: Mock = from_entries(&[("row".into(), "10".into())]).unwrap();
+        assert_eq!(params, Mock { row: 10 });
+        assert!(from_entries::<Mock>(&[("col".into(), "10".into())]).is_err());
+        assert!(from_entries::<Mock>(&[("row".into(), "ten".into())]).is_err());
+    }
+}
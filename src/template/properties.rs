@@ -0,0 +1,90 @@
+/// Property tests over seeded random cases. A case is generated from a `StdRng` seeded with 0, 1, 2, ... up to
+/// `AOC_CASES` (200 by default), so every failure can be reproduced: it panics with the seed and the generated input,
+/// and `AOC_SEED=<seed>` runs just that case. Days compare their parts with a slow reference using `reference_tests!`.
+use rand::{rngs::StdRng, SeedableRng};
+use std::{env, fmt::Debug};
+
+pub const CASES: u64 = 200;
+
+/// The seeds to run: `AOC_SEED` if set, otherwise `0..AOC_CASES`.
+pub fn seeds() -> Vec<u64> {
+    let var = |name: &str| env::var(name).ok().and_then(|v| v.parse::<u64>().ok());
+
+    match var("AOC_SEED") {
+        Some(seed) => vec![seed],
+        None => (0..var("AOC_CASES").unwrap_or(CASES)).collect(),
+    }
+}
+
+/// Generate a case for every seed and run `check` on it.
+pub fn check<G: Debug>(
+    generate: impl Fn(&mut StdRng) -> G,
+    check: impl Fn(&G) -> Result<(), String>,
+) {
+    for seed in seeds() {
+        let case = generate(&mut StdRng::seed_from_u64(seed));
+
+        if let Err(e) = check(&case) {
+            panic!(
+                "case failed with AOC_SEED={}: {}\ninput: {:?}",
+                seed, e, case
+            );
+        }
+    }
+}
+
+/// Check that `fast` computes the same result as `reference` for every generated case.
+pub fn compare<G: Debug, T: PartialEq + Debug>(
+    generate: impl Fn(&mut StdRng) -> G,
+    fast: impl Fn(&G) -> T,
+    reference: impl Fn(&G) -> T,
+) {
+    check(generate, |case| {
+        let (actual, expected) = (fast(case), reference(case));
+        if actual == expected {
+            Ok(())
+        } else {
+            Err(format!(
+                "expected {:?} (reference), got {:?}",
+                expected, actual
+            ))
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check, compare};
+    use rand::Rng;
+
+    #[test]
+    fn runs_every_seed() {
+        compare(
+            |rng| {
+                (0..rng.gen_range(0..20))
+                    .map(|_| rng.gen_range(0..100))
+                    .collect::<Vec<u32>>()
+            },
+            |v| v.iter().sum::<u32>(),
+            |v| {
+                let mut sum = 0;
+                for x in v {
+                    sum += x;
+                }
+                sum
+            },
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "case failed with AOC_SEED=")]
+    fn reports_the_seed() {
+        check(
+            |rng| rng.gen_range(0..10),
+            |n| match n {
+                0..=4 => Ok(()),
+                _ => Err("too large".into()),
+            },
+        );
+    }
+}
@@ -1,8 +1,14 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+/// The table goes between two `<!--- benchmarking table --->` markers. A bar chart is written to
+/// `.assets/benchmarks.svg`, and embedded between two `<!--- benchmarking chart --->` markers if the README has them.
+use std::{fs, io, time::Duration};
+
+use crate::template::aoc_client;
 
 static MARKER: &str = "<!--- benchmarking table --->";
+static CHART_MARKER: &str = "<!--- benchmarking chart --->";
+static CHART_PATH: &str = ".assets/benchmarks.svg";
 
 #[derive(Debug)]
 pub enum Error {
@@ -16,18 +22,45 @@ impl From<std::io::Error> for Error {
     }
 }
 
+/// Bench statistics of one label, as printed by the runner.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub samples: u128,
+    pub median: String,
+    /// Standard deviation of the samples.
+    pub spread: String,
+}
+
 #[derive(Clone)]
 pub struct Timings {
     pub day: usize,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub parser: Option<String>,
+    /// Time of both parts of a day using `main!(day, solve)`, which are not timed separately.
+    pub combined: Option<String>,
     pub total_nanos: f64,
+    /// Raw timings in nanoseconds, keyed by label (`Parser`, `Part 1`, `Part 2`).
+    pub nanos: Vec<(String, f64)>,
+    /// Bench statistics, keyed by label. Empty unless the day was benched.
+    pub stats: Vec<(String, Stats)>,
+    /// Peak resident memory of the solution process.
+    pub memory: Option<String>,
+}
+
+impl Timings {
+    fn get_nanos(&self, label: &str) -> Option<f64> {
+        self.nanos.iter().find(|(l, _)| l == label).map(|(_, n)| *n)
+    }
+
+    fn get_stats(&self, label: &str) -> Option<&Stats> {
+        self.stats.iter().find(|(l, _)| l == label).map(|(_, s)| s)
+    }
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub pos_start: usize,
+    pub pos_end: usize,
 }
 
 pub fn get_path_for_bin(day: usize) -> String {
@@ -35,8 +68,30 @@ pub fn get_path_for_bin(day: usize) -> String {
     format!("./src/bin/{}.rs", day_padded)
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos as u64))
+}
+
+/// Occurrences of a marker, skipping those inside fenced code blocks, which show the markers in documentation.
+pub(crate) fn find_markers<'a>(readme: &'a str, marker: &'a str) -> Vec<(usize, &'a str)> {
+    let mut matches = vec![];
+    let mut offset = 0;
+    let mut in_fence = false;
+
+    for line in readme.split_inclusive('\n') {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        } else if !in_fence {
+            matches.extend(line.match_indices(marker).map(|(i, m)| (offset + i, m)));
+        }
+        offset += line.len();
+    }
+
+    matches
+}
+
+pub(crate) fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches = find_markers(readme, marker);
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -57,29 +112,87 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
+/// A table cell with the mean time, and median, spread and sample count if the part was benched.
+fn format_cell(timing: &Timings, label: &str, value: Option<String>) -> String {
+    let value = format!("`{}`", value.unwrap_or_else(|| "-".into()));
+
+    match timing.get_stats(label) {
+        Some(stats) => format!(
+            "{} <sub>median {} ± {}, {} samples</sub>",
+            value, stats.median, stats.spread, stats.samples
+        ),
+        None => value,
+    }
+}
+
+fn construct_table(prefix: &str, year: u16, timings: Vec<Timings>, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
+    let total_nanos = timings.iter().map(|t| t.total_nanos).sum::<f64>();
 
     let mut lines: Vec<String> = vec![
         MARKER.into(),
         header,
         "".into(),
-        "| Day | Parser | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---: | :---:  |".into(),
+        "| Day | Parser | Part 1 | Part 2 | Total | Share | Memory |".into(),
+        "| :---: | :---: | :---: | :---: | :---: | :---: | :---: |".into(),
     ];
 
-    timings.into_iter().for_each(|timing| {
+    // a column without any measurement shows `-`, like the cells of the days.
+    let sum = |labels: &[&str]| -> String {
+        let nanos: Vec<f64> = timings
+            .iter()
+            .flat_map(|t| labels.iter().filter_map(|label| t.get_nanos(label)))
+            .collect();
+        match nanos.is_empty() {
+            true => "`-`".into(),
+            false => format!("`{}`", format_nanos(nanos.iter().sum())),
+        }
+    };
+    // a combined run is shown in the part 1 column, so it counts towards its total.
+    let totals = format!(
+        "| **Total** | {} | {} | {} | **`{}`** | 100% | |",
+        sum(&["Parser"]),
+        sum(&["Part 1", "Combined"]),
+        sum(&["Part 2"]),
+        format_nanos(total_nanos)
+    );
+
+    timings.iter().for_each(|timing| {
         let path = get_path_for_bin(timing.day);
+
+        // a combined run spans both part columns.
+        let (part_1, part_2) = match &timing.combined {
+            Some(combined) => (
+                format_cell(timing, "Combined", Some(combined.clone())),
+                "*combined*".to_string(),
+            ),
+            None => (
+                format_cell(timing, "Part 1", timing.part_1.clone()),
+                format_cell(timing, "Part 2", timing.part_2.clone()),
+            ),
+        };
+
+        let share = match total_nanos > 0.0 {
+            true => format!("{:.1}%", timing.total_nanos / total_nanos * 100.0),
+            false => "-".into(),
+        };
+
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` | `{}` |",
+            "| [Day {}](https://adventofcode.com/{}/day/{}) · [solution]({}) | {} | {} | {} | `{}` | {} | `{}` |",
+            timing.day,
+            year,
             timing.day,
             path,
-            timing.parser.unwrap_or_else(|| "-".into()),
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            format_cell(timing, "Parser", timing.parser.clone()),
+            part_1,
+            part_2,
+            format_nanos(timing.total_nanos),
+            share,
+            timing.memory.clone().unwrap_or_else(|| "-".into())
         ));
     });
 
+    lines.push(totals);
     lines.push("".into());
     lines.push(format!("**Total: {:.2}ms**", total_millis));
     lines.push(MARKER.into());
@@ -87,24 +200,126 @@ fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> St
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+/// Escape text for use in SVG.
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// A horizontal bar per day, split into the time of the parser and each part.
+fn construct_chart(timings: &[Timings]) -> String {
+    const WIDTH: f64 = 800.0;
+    const LABEL_WIDTH: f64 = 70.0;
+    const VALUE_WIDTH: f64 = 90.0;
+    const ROW_HEIGHT: f64 = 22.0;
+    const TOP: f64 = 36.0;
+    const SEGMENTS: [(&str, &str); 4] = [
+        ("Parser", "#8da0cb"),
+        ("Part 1", "#66c2a5"),
+        ("Part 2", "#fc8d62"),
+        ("Combined", "#e78ac3"),
+    ];
+
+    let height = TOP + ROW_HEIGHT * timings.len() as f64 + 10.0;
+    let bar_width = WIDTH - LABEL_WIDTH - VALUE_WIDTH;
+    let max_nanos = timings
+        .iter()
+        .map(|t| t.total_nanos)
+        .fold(0.0, f64::max)
+        .max(1.0);
+
+    let mut svg = vec![
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH}\" height=\"{height}\" viewBox=\"0 0 {WIDTH} {height}\" font-family=\"sans-serif\" font-size=\"12\">"
+        ),
+        format!("<rect width=\"{WIDTH}\" height=\"{height}\" fill=\"#ffffff\"/>"),
+    ];
+
+    for (i, (label, color)) in SEGMENTS.iter().enumerate() {
+        let x = LABEL_WIDTH + i as f64 * 100.0;
+        svg.push(format!(
+            "<rect x=\"{x}\" y=\"8\" width=\"12\" height=\"12\" fill=\"{color}\"/><text x=\"{}\" y=\"18\">{label}</text>",
+            x + 16.0
+        ));
+    }
+
+    for (row, timing) in timings.iter().enumerate() {
+        let y = TOP + row as f64 * ROW_HEIGHT;
+        svg.push(format!(
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">Day {}</text>",
+            LABEL_WIDTH - 8.0,
+            y + 14.0,
+            timing.day
+        ));
+
+        let mut x = LABEL_WIDTH;
+        for (label, color) in SEGMENTS {
+            let nanos = match timing.get_nanos(label) {
+                Some(nanos) if nanos > 0.0 => nanos,
+                _ => continue,
+            };
+            let width = nanos / max_nanos * bar_width;
+            svg.push(format!(
+                "<rect x=\"{x:.1}\" y=\"{y}\" width=\"{width:.1}\" height=\"{}\" fill=\"{color}\"><title>Day {} {}: {}</title></rect>",
+                ROW_HEIGHT - 6.0,
+                timing.day,
+                label,
+                escape(&format_nanos(nanos))
+            ));
+            x += width;
+        }
+
+        svg.push(format!(
+            "<text x=\"{:.1}\" y=\"{}\">{}</text>",
+            x + 6.0,
+            y + 14.0,
+            escape(&format_nanos(timing.total_nanos))
+        ));
+    }
+
+    svg.push("</svg>".into());
+    svg.join("\n") + "\n"
+}
+
+fn update_content(
+    s: &mut String,
+    year: u16,
+    timings: Vec<Timings>,
+    total_millis: f64,
+) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table("##", year, timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
+
+    // the chart is optional, but has to be well-formed if present.
+    if !find_markers(s, CHART_MARKER).is_empty() {
+        let positions = locate_table(s, CHART_MARKER)?;
+        let chart = format!(
+            "{}\n![Benchmarks](./{})\n{}",
+            CHART_MARKER, CHART_PATH, CHART_MARKER
+        );
+        s.replace_range(positions.pos_start..positions.pos_end, &chart);
+    }
+
     Ok(())
 }
 
 pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
+    let chart = construct_chart(&timings);
+    update_content(&mut readme, aoc_client::get_year(), timings, total_millis)?;
     fs::write(path, &readme)?;
+
+    fs::create_dir_all(".assets")?;
+    fs::write(CHART_PATH, chart)?;
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{update_content, Timings, MARKER};
+    use super::{construct_chart, update_content, Stats, Timings, CHART_MARKER, MARKER};
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
@@ -113,21 +328,40 @@ mod tests {
                 part_1: Some("10ms".into()),
                 part_2: Some("20ms".into()),
                 parser: None,
-                total_nanos: 3e+10,
+                combined: None,
+                total_nanos: 3e+7,
+                nanos: vec![("Part 1".into(), 1e+7), ("Part 2".into(), 2e+7)],
+                stats: vec![(
+                    "Part 1".into(),
+                    Stats {
+                        samples: 100,
+                        median: "9.8ms".into(),
+                        spread: "0.4ms".into(),
+                    },
+                )],
+                memory: Some("1.2MiB".into()),
             },
             Timings {
                 day: 2,
                 part_1: Some("30ms".into()),
                 part_2: Some("40ms".into()),
                 parser: None,
-                total_nanos: 7e+10,
+                combined: None,
+                total_nanos: 7e+7,
+                nanos: vec![("Part 1".into(), 3e+7), ("Part 2".into(), 4e+7)],
+                stats: vec![],
+                memory: None,
             },
             Timings {
                 day: 4,
-                part_1: Some("40ms".into()),
-                part_2: Some("50ms".into()),
+                part_1: None,
+                part_2: None,
                 parser: None,
-                total_nanos: 9e+10,
+                combined: Some("90ms".into()),
+                total_nanos: 9e+7,
+                nanos: vec![("Combined".into(), 9e+7)],
+                stats: vec![],
+                memory: None,
             },
         ]
     }
@@ -136,28 +370,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, 2022, get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, 2022, get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, 2022, get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, 2022, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, 2022, get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -165,18 +399,19 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, 2022, get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Parser | Part 1 | Part 2 |",
-            "| :---: | :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `-` | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `-` | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `-` | `40ms` | `50ms` |",
+            "| Day | Parser | Part 1 | Part 2 | Total | Share | Memory |",
+            "| :---: | :---: | :---: | :---: | :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2022/day/1) · [solution](./src/bin/01.rs) | `-` | `10ms` <sub>median 9.8ms ± 0.4ms, 100 samples</sub> | `20ms` | `30.0ms` | 15.8% | `1.2MiB` |",
+            "| [Day 2](https://adventofcode.com/2022/day/2) · [solution](./src/bin/02.rs) | `-` | `30ms` | `40ms` | `70.0ms` | 36.8% | `-` |",
+            "| [Day 4](https://adventofcode.com/2022/day/4) · [solution](./src/bin/04.rs) | `-` | `90ms` | *combined* | `90.0ms` | 47.4% | `-` |",
+            "| **Total** | `-` | `130.0ms` | `60.0ms` | **`190.0ms`** | 100% | |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -185,4 +420,30 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn updates_chart_block() {
+        let mut s = format!(
+            "{}{}\nfoo\n{}{}",
+            MARKER, MARKER, CHART_MARKER, CHART_MARKER
+        );
+        update_content(&mut s, 2022, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, 2022, get_mock_timings(), 190.0).unwrap();
+        assert_eq!(s.matches(CHART_MARKER).count(), 2);
+        assert_eq!(
+            s.matches("![Benchmarks](./.assets/benchmarks.svg)").count(),
+            1
+        );
+        assert!(s.contains("## Benchmarks"));
+    }
+
+    #[test]
+    fn draws_chart() {
+        let svg = construct_chart(&get_mock_timings());
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<title>").count(), 5);
+        assert!(svg.contains("<title>Day 4 Combined: 90.0ms</title>"));
+        assert!(svg.contains(">Day 2</text>"));
+    }
 }
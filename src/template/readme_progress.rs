@@ -0,0 +1,203 @@
+/// Module that updates the readme with the progress of each day, without talking to adventofcode.com.
+/// A run of `cargo all` over every day records which days are scaffolded, which parts return an answer and which
+/// answers match `data/answers`. The table goes between two `<!--- progress table --->` markers, if the README has them
+/// outside of a code block.
+use std::fs;
+
+use crate::template::{
+    answers::Check,
+    aoc_client,
+    commands::all::get_path_for_bin,
+    readme_benchmarks::{find_markers, locate_table, Error},
+};
+
+static MARKER: &str = "<!--- progress table --->";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartStatus {
+    /// The part returns `None`, or the day did not run.
+    Missing,
+    /// The part returns an answer, but there is no stored answer to compare it with.
+    Solved,
+    Correct,
+    Incorrect,
+}
+
+impl PartStatus {
+    fn symbol(&self) -> &'static str {
+        match self {
+            PartStatus::Missing => "·",
+            PartStatus::Solved => "?",
+            PartStatus::Correct => "⭐",
+            PartStatus::Incorrect => "✗",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Progress {
+    pub day: usize,
+    pub scaffolded: bool,
+    pub parts: [PartStatus; 2],
+}
+
+impl Progress {
+    /// The progress of a day, from the answer checks of its run.
+    pub fn from_checks(day: usize, checks: &[(u8, Check)]) -> Self {
+        let status = |part: u8| match checks.iter().find(|(p, _)| *p == part) {
+            Some((_, Check::Correct)) => PartStatus::Correct,
+            Some((_, Check::Incorrect)) => PartStatus::Incorrect,
+            Some((_, Check::Unknown)) => PartStatus::Solved,
+            None => PartStatus::Missing,
+        };
+
+        Progress {
+            day,
+            scaffolded: fs::metadata(get_path_for_bin(day)).is_ok(),
+            parts: [status(1), status(2)],
+        }
+    }
+
+    fn stars(&self) -> usize {
+        self.parts
+            .iter()
+            .filter(|part| **part == PartStatus::Correct)
+            .count()
+    }
+}
+
+fn construct_table(prefix: &str, year: u16, progress: &[Progress]) -> String {
+    let header = format!("{prefix} Progress");
+    let stars = progress.iter().map(Progress::stars).sum::<usize>();
+
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        header,
+        "".into(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    progress.iter().for_each(|day| {
+        let link = format!(
+            "[Day {}](https://adventofcode.com/{}/day/{})",
+            day.day, year, day.day
+        );
+
+        if day.scaffolded {
+            lines.push(format!(
+                "| {} | {} | {} |",
+                link,
+                day.parts[0].symbol(),
+                day.parts[1].symbol()
+            ));
+        } else {
+            lines.push(format!("| {} | | |", link));
+        }
+    });
+
+    lines.push("".into());
+    lines.push(format!("**Stars: {}/{}**", stars, progress.len() * 2));
+    lines.push("".into());
+    lines.push(
+        "⭐ matches the stored answer, ? has no stored answer, ✗ does not match, · not solved yet"
+            .into(),
+    );
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, year: u16, progress: &[Progress]) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table("##", year, progress);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+/// Write the progress table into the README. Returns `false` if the README has no progress markers.
+pub fn update(progress: &[Progress]) -> Result<bool, Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+
+    if find_markers(&readme, MARKER).is_empty() {
+        return Ok(false);
+    }
+
+    update_content(&mut readme, aoc_client::get_year(), progress)?;
+    fs::write(path, &readme)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{update_content, PartStatus, Progress, MARKER};
+
+    fn get_mock_progress() -> Vec<Progress> {
+        vec![
+            Progress {
+                day: 1,
+                scaffolded: true,
+                parts: [PartStatus::Correct, PartStatus::Correct],
+            },
+            Progress {
+                day: 2,
+                scaffolded: true,
+                parts: [PartStatus::Incorrect, PartStatus::Solved],
+            },
+            Progress {
+                day: 3,
+                scaffolded: true,
+                parts: [PartStatus::Correct, PartStatus::Missing],
+            },
+            Progress {
+                day: 4,
+                scaffolded: false,
+                parts: [PartStatus::Missing, PartStatus::Missing],
+            },
+        ]
+    }
+
+    #[test]
+    fn format_progress() {
+        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, 2022, &get_mock_progress()).unwrap();
+
+        let expected = [
+            "foo",
+            "bar",
+            "<!--- progress table --->",
+            "## Progress",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2022/day/1) | ⭐ | ⭐ |",
+            "| [Day 2](https://adventofcode.com/2022/day/2) | ✗ | ? |",
+            "| [Day 3](https://adventofcode.com/2022/day/3) | ⭐ | · |",
+            "| [Day 4](https://adventofcode.com/2022/day/4) | | |",
+            "",
+            "**Stars: 3/8**",
+            "",
+            "⭐ matches the stored answer, ? has no stored answer, ✗ does not match, · not solved yet",
+            "<!--- progress table --->",
+            "baz",
+        ]
+        .join("\n");
+
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn ignores_markers_in_code_blocks() {
+        let docs = format!("```markdown\n{}\n{}\n```\n", MARKER, MARKER);
+        let mut s = format!("{}foo\n{}\n{}\n", docs, MARKER, MARKER);
+        update_content(&mut s, 2022, &get_mock_progress()).unwrap();
+
+        assert!(s.starts_with(&format!("{}foo\n{}\n## Progress", docs, MARKER)));
+        assert_eq!(s.matches("## Progress").count(), 1);
+
+        let mut s = docs.clone();
+        assert!(update_content(&mut s, 2022, &get_mock_progress()).is_err());
+        assert_eq!(s, docs);
+    }
+}
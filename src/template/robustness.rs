@@ -0,0 +1,233 @@
+/// Robustness checks of a day's parser. With `--robustness`, a day's binary feeds its example and real input to
+/// `parse`, each with mutations such as truncation, CRLF line endings or flipped bytes, and reports the mutations
+/// that make the parser panic, hang or run out of memory instead of failing gracefully. Every input is parsed in a
+/// child process of the binary, started with `--robustness-case <source> <index>`, so that such a failure only ends
+/// that child. `tests/parsers.rs` runs this for every day.
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{
+    env,
+    io::Read,
+    panic::{self, PanicHookInfo},
+    process::{self, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::template::{input, ANSI_BOLD, ANSI_RESET};
+
+/// Number of inputs with a randomly flipped byte, per source.
+const FLIPS: u64 = 10;
+/// How long a parser may take for one input before it is reported as hanging.
+const TIMEOUT: Duration = Duration::from_secs(5);
+const CASE_FLAG: &str = "--robustness-case";
+
+pub fn is_requested() -> bool {
+    env::args().any(|x| x == "--robustness" || x == CASE_FLAG)
+}
+
+/// Cut an input after at most `len` bytes, at a char boundary.
+fn truncate(input: &str, len: usize) -> String {
+    let mut end = len.min(input.len());
+    while !input.is_char_boundary(end) {
+        end -= 1;
+    }
+    input[..end].to_string()
+}
+
+/// Replace a random ASCII character of the input with a random printable one.
+fn flip_byte(input: &str, seed: u64) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let positions: Vec<usize> = input
+        .char_indices()
+        .filter(|(_, c)| c.is_ascii())
+        .map(|(i, _)| i)
+        .collect();
+
+    if positions.is_empty() {
+        return input.to_string();
+    }
+
+    let pos = positions[rng.gen_range(0..positions.len())];
+    let mut bytes = input.as_bytes().to_vec();
+    bytes[pos] = rng.gen_range(b' '..=b'~');
+    // only an ASCII byte was replaced by another, so the input is still valid UTF-8.
+    String::from_utf8(bytes).unwrap_or_default()
+}
+
+/// The mutations of an input, by name. The unchanged input comes first.
+pub fn mutations(input: &str) -> Vec<(String, String)> {
+    let mut mutations: Vec<(String, String)> = vec![
+        ("unchanged".into(), input.to_string()),
+        ("empty".into(), String::new()),
+        ("truncated to half".into(), truncate(input, input.len() / 2)),
+        (
+            "truncated mid-line".into(),
+            truncate(input, input.trim_end().len().saturating_sub(1)),
+        ),
+        (
+            "truncated to the first line".into(),
+            input.lines().next().unwrap_or_default().to_string(),
+        ),
+        ("extra blank lines".into(), format!("{}\n\n\n", input)),
+        (
+            "trailing spaces".into(),
+            input.lines().map(|l| format!("{}  \n", l)).collect(),
+        ),
+        ("CRLF line endings".into(), input.replace('\n', "\r\n")),
+        (
+            "no final newline".into(),
+            input.trim_end_matches('\n').to_string(),
+        ),
+    ];
+
+    mutations.extend(
+        (0..FLIPS).map(|seed| (format!("byte flip (seed {})", seed), flip_byte(input, seed))),
+    );
+    mutations
+}
+
+/// Print a panic as a single line, which the parent process reports.
+fn print_panic(info: &PanicHookInfo) {
+    let payload = info.payload();
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_default();
+    let location = info
+        .location()
+        .map(|l| format!(" at {}:{}", l.file(), l.line()))
+        .unwrap_or_default();
+
+    eprintln!("{}{}", message, location);
+}
+
+fn read_source(name: &str, day: u8) -> Result<String, input::Error> {
+    match name {
+        "Example" => input::read_file("examples", day),
+        _ => input::read_file("inputs", day),
+    }
+}
+
+/// Parse one mutated input, in the child process.
+fn run_case<T>(day: u8, parse: impl Fn(&'static str) -> T, source: &str, index: usize) {
+    panic::set_hook(Box::new(print_panic));
+
+    let source = read_source(source, day).unwrap_or_else(|e| panic!("{}", e));
+    let (_, input) = mutations(&source).swap_remove(index);
+    // parsers may borrow from their input, as they do in `main!`.
+    parse(Box::leak(input.into_boxed_str()));
+}
+
+/// Parse one mutated input in a child process. Returns why it failed, if it did.
+fn check_case(source: &str, index: usize) -> Result<(), String> {
+    let exe = env::current_exe().map_err(|e| e.to_string())?;
+    let mut child = Command::new(exe)
+        .args([CASE_FLAG, source, &index.to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+
+    let start = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(|e| e.to_string())? {
+            break status;
+        }
+        if start.elapsed() > TIMEOUT {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(format!("timed out after {:.1?}", TIMEOUT));
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+
+    if status.success() {
+        return Ok(());
+    }
+
+    let mut stderr = String::new();
+    if let Some(mut pipe) = child.stderr.take() {
+        let _ = pipe.read_to_string(&mut stderr);
+    }
+
+    match (status.code(), stderr.lines().next()) {
+        (Some(_), Some(message)) => Err(message.to_string()),
+        (Some(code), None) => Err(format!("exited with {}", code)),
+        // e.g. killed for running out of memory.
+        (None, _) => Err("killed by a signal".into()),
+    }
+}
+
+/// Run the parser on every mutation of the example and the real input, and exit with an error if some failed.
+pub fn run<T>(day: u8, parse: impl Fn(&'static str) -> T) {
+    let args: Vec<String> = env::args().collect();
+    if let Some(index) = args.iter().position(|x| x == CASE_FLAG) {
+        let source = args.get(index + 1).map(String::as_str).unwrap_or_default();
+        let case = args
+            .get(index + 2)
+            .and_then(|x| x.parse().ok())
+            .unwrap_or(0);
+        run_case(day, parse, source, case);
+        return;
+    }
+
+    let mut total = 0;
+    let mut failures = 0;
+
+    for name in ["Example", "Input"] {
+        let source = match read_source(name, day) {
+            Ok(source) => source,
+            Err(e) => {
+                println!("{}: skipped, {}", name, e);
+                continue;
+            }
+        };
+
+        println!("{}{}{}", ANSI_BOLD, name, ANSI_RESET);
+
+        for (index, (mutation, _)) in mutations(&source).iter().enumerate() {
+            total += 1;
+
+            match check_case(name, index) {
+                Ok(_) => println!("  ✓ {}", mutation),
+                Err(e) => {
+                    failures += 1;
+                    println!("  ✖ {}: {}", mutation, e);
+                }
+            }
+        }
+    }
+
+    println!("Robustness: {} of {} inputs failed", failures, total);
+    if failures > 0 {
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mutations;
+
+    #[test]
+    fn mutates_inputs() {
+        let input = "1,2\n3,4\n";
+        let mutations = mutations(input);
+        let get = |name: &str| {
+            mutations
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, input)| input.as_str())
+                .unwrap()
+        };
+
+        assert_eq!(get("unchanged"), input);
+        assert_eq!(get("truncated to half"), "1,2\n");
+        assert_eq!(get("truncated mid-line"), "1,2\n3,");
+        assert_eq!(get("trailing spaces"), "1,2  \n3,4  \n");
+        assert_eq!(get("CRLF line endings"), "1,2\r\n3,4\r\n");
+        assert_eq!(get("no final newline"), "1,2\n3,4");
+        assert_eq!(get("byte flip (seed 0)").len(), input.len());
+        assert_ne!(get("byte flip (seed 0)"), get("byte flip (seed 1)"));
+    }
+}
@@ -1,31 +1,259 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
+use crate::ocr;
+use crate::parse::IntoParsed;
+use crate::template::alloc;
+use crate::template::answers::{self, Check};
+use crate::template::commands::solve::check_examples;
+#[cfg(feature = "viz")]
+use crate::template::viz;
+use crate::template::{aoc_client, input, spans, submissions, ANSI_ITALIC, ANSI_RESET};
 use std::fmt::Display;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::sync::mpsc::{self, RecvTimeoutError};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, fs, process, thread};
 
 use super::ANSI_BOLD;
 
-pub fn run_parser<I: Clone, T>(func: impl Fn(I) -> T, input: I) -> T {
-    let (result, duration, samples) = run_timed(func, input, |_| {
-        print_result(&Some("✓"), "Parser", "");
-    });
-    print_result(&Some("✓"), "Parser", &format_duration(&duration, samples));
-    result
+/// Run the day's `parse`, which returns either the input or a `Result`. A `ParseError` is printed as a diagnostic,
+/// and ends the process.
+pub fn run_parser<I: Clone, T, M, R: IntoParsed<T, M>>(func: impl Fn(I) -> R, input: I) -> T {
+    label_frames("Parser");
+    let symbol = |result: &Result<T, _>| Some(if result.is_ok() { "✓" } else { "✖" });
+    let (result, timing, allocations) = run_timed(
+        |input| func(input).into_parsed(),
+        input,
+        |result| {
+            print_result(&symbol(result), "Parser", "", "");
+        },
+    );
+    print_result(&symbol(&result), "Parser", "", &format_duration(&timing));
+    print_allocations(&allocations);
+    print_spans();
+    print_frames();
+
+    result.unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    })
 }
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: u8, part: u8) {
+pub fn run_part<I, T>(
+    func: impl Fn(I) -> Option<T> + Clone + Send + 'static,
+    input: I,
+    day: u8,
+    part: u8,
+) where
+    I: Clone + Send + 'static,
+    T: Display + Send + 'static,
+{
+    if get_selected_part().is_some_and(|selected| selected != part) {
+        return;
+    }
+
     let part_str = format!("Part {}", part);
+    label_frames(&part_str);
+    let hook = |result: &Option<T>| print_result(result, &part_str, "", "");
+
+    let (result, timing, allocations) = match run_measured(func, input, hook) {
+        Ok(run) => run,
+        Err(timeout) => {
+            println!("{}: timed out after {:.1?}", part_str, timeout);
+            return;
+        }
+    };
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let check = match &result {
+        Some(result) => check_result(&to_answer(result), day, part),
+        None => String::new(),
+    };
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_result(&result, &part_str, &check, &format_duration(&timing));
+    print_allocations(&allocations);
+    print_spans();
+    print_frames();
 
     if let Some(result) = result {
-        submit_result(result, day, part);
+        submit_result(to_answer(&result), day, part);
+    }
+}
+
+/// Run a `solve` function that computes both parts in one pass. It is timed as a whole under the label `Combined`,
+/// while the two answers are still checked, printed and submitted independently.
+pub fn run_combined<I, A, B>(
+    func: impl Fn(I) -> (Option<A>, Option<B>) + Clone + Send + 'static,
+    input: I,
+    day: u8,
+) where
+    I: Clone + Send + 'static,
+    A: Display + Send + 'static,
+    B: Display + Send + 'static,
+{
+    label_frames("Combined");
+    let hook = |_: &(Option<A>, Option<B>)| print_result(&Some("✓"), "Combined", "", "");
+
+    let ((part_one, part_two), timing, allocations) = match run_measured(func, input, hook) {
+        Ok(run) => run,
+        Err(timeout) => {
+            // reported per part, so that `all` counts both as timed out.
+            for part in 1..=2 {
+                println!("Part {}: timed out after {:.1?}", part, timeout);
+            }
+            return;
+        }
+    };
+
+    print_result(&Some("✓"), "Combined", "", &format_duration(&timing));
+    print_allocations(&allocations);
+    print_spans();
+    print_frames();
+
+    print_answer(part_one, day, 1);
+    print_answer(part_two, day, 2);
+}
+
+/// Print and submit one answer of a combined run. Its time is part of the `Combined` line.
+fn print_answer<T: Display>(result: Option<T>, day: u8, part: u8) {
+    let check = match &result {
+        Some(result) => check_result(&to_answer(result), day, part),
+        None => String::new(),
+    };
+
+    // printed like a finished part, so that `all` picks up the check.
+    let part_str = format!("Part {}", part);
+    print_result(&result, &part_str, "", "");
+    print_result(&result, &part_str, &check, " (combined)");
+
+    if let Some(result) = result {
+        submit_result(to_answer(&result), day, part);
+    }
+}
+
+/// Whether `--variants` was passed, i.e. the variants registered with `variants!` should run instead of the parts.
+pub fn is_variants_run() -> bool {
+    env::args().any(|x| x == "--variants")
+}
+
+/// A named implementation of a part, as registered with `variants!`.
+pub type Variant<I, T> = (&'static str, fn(I) -> Option<T>);
+
+/// Run a part and its variants on the same input. Each is benched, its result is compared with the part's and
+/// the timings are printed side by side. Returns whether all variants agree with the part.
+pub fn run_variants<I: Clone, T: Display>(
+    label: &str,
+    input: I,
+    variants: &[Variant<I, T>],
+) -> bool {
+    println!("{}{}{}", ANSI_BOLD, label, ANSI_RESET);
+
+    let mut expected = None;
+    let mut agree = true;
+    let mut rows = vec![];
+
+    for (i, (name, func)) in variants.iter().enumerate() {
+        label_frames(name);
+        let (result, base_time, _) = run_once(func, input.clone());
+        print_result(&result, name, "", "");
+        let timing = bench(*func, input.clone(), &base_time);
+
+        let answer = result.as_ref().map(to_answer);
+        let check = if i == 0 {
+            expected = answer;
+            String::new()
+        } else if answer == expected {
+            format!(" {}", Check::Correct.symbol())
+        } else {
+            agree = false;
+            format!(" {} differs from {}", Check::Incorrect.symbol(), label)
+        };
+
+        print_result(&result, name, &check, &format_duration(&timing));
+        print_frames();
+        rows.push((*name, timing));
+    }
+
+    println!();
+    format_comparison(&rows)
+        .iter()
+        .for_each(|line| println!("{}", line));
+    println!();
+
+    agree
+}
+
+/// Exit with an error if some variants did not agree with their part.
+pub fn finish_variants(day: u8, agree: bool) {
+    if !agree {
+        eprintln!("The variants of day {} do not agree.", day);
+        process::exit(1);
+    }
+}
+
+/// Used by `main!` for days without a `variants!` registration, which shadows it.
+pub mod no_variants {
+    pub fn __run_variants(_input: &'static str, day: u8) {
+        eprintln!(
+            "Day {} has no variants, register them with `advent_of_code::variants!` next to `main!(day)`.",
+            day
+        );
+        std::process::exit(1);
+    }
+}
+
+/// A table of the variants' timings, relative to the first row.
+fn format_comparison(rows: &[(&str, Timing)]) -> Vec<String> {
+    let width = rows.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    let base = rows
+        .first()
+        .map(|(_, timing)| timing.mean.as_nanos().max(1) as f64)
+        .unwrap_or(1.0);
+
+    rows.iter()
+        .map(|(name, timing)| {
+            format!(
+                "  {:<width$}  {:>9}  median {:>9} ± {:<9}  {:.2}×",
+                name,
+                format!("{:.1?}", timing.mean),
+                format!("{:.1?}", timing.median),
+                format!("{:.1?}", timing.spread),
+                timing.mean.as_nanos() as f64 / base,
+                width = width
+            )
+        })
+        .collect()
+}
+
+/// The answer for a result as it is checked and submitted. Drawings of block letters are read with OCR.
+pub fn to_answer<T: Display>(result: &T) -> String {
+    let result = result.to_string();
+    if !result.contains('\n') {
+        return result;
+    }
+
+    ocr::read_str(&result).unwrap_or(result)
+}
+
+/// Time a function, on a separate thread if `--timeout` was passed. Returns the timeout if it was exceeded.
+fn run_measured<I, T>(
+    func: impl Fn(I) -> T + Clone + Send + 'static,
+    input: I,
+    hook: impl Fn(&T),
+) -> Result<(T, Timing, Option<alloc::Stats>), Duration>
+where
+    I: Clone + Send + 'static,
+    T: Send + 'static,
+{
+    match get_timeout() {
+        None => Ok(run_timed(func, input, hook)),
+        Some(timeout) => match run_with_timeout(func.clone(), input.clone(), timeout) {
+            Ok((result, base_time, allocations)) => {
+                let timing = finish_timed(func, input, &result, base_time, hook);
+                Ok((result, timing, allocations))
+            }
+            Err(RecvTimeoutError::Timeout) => Err(timeout),
+            // the part panicked, its message has already been printed by the panic hook.
+            Err(RecvTimeoutError::Disconnected) => process::exit(101),
+        },
     }
 }
 
@@ -36,22 +264,171 @@ fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Timing, Option<alloc::Stats>) {
+    let (result, base_time, allocations) = run_once(&func, input.clone());
+    let timing = finish_timed(func, input, &result, base_time, hook);
+    (result, timing, allocations)
+}
+
+/// Run a function once. Allocations, spans and frames are only tracked for this first run, not while benching.
+fn run_once<I, T>(func: &impl Fn(I) -> T, input: I) -> (T, Duration, Option<alloc::Stats>) {
+    spans::start();
+    #[cfg(feature = "viz")]
+    viz::start();
+    let tracker = alloc::Tracker::start();
     let timer = Instant::now();
-    let result = func(input.clone());
-    let base_time = timer.elapsed();
+    let result = func(input);
+    let duration = timer.elapsed();
+    #[cfg(feature = "viz")]
+    viz::stop();
+    spans::stop();
+    (result, duration, tracker.finish())
+}
 
-    hook(&result);
+/// Report the result of the first run and bench the function if `--time` was passed.
+fn finish_timed<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    result: &T,
+    base_time: Duration,
+    hook: impl Fn(&T),
+) -> Timing {
+    hook(result);
 
-    let run = match std::env::args().any(|x| x == "--time") {
+    match std::env::args().any(|x| x == "--time") {
         true => bench(func, input, &base_time),
-        false => (base_time, 1),
-    };
+        false => Timing {
+            mean: base_time,
+            median: base_time,
+            spread: Duration::ZERO,
+            samples: 1,
+        },
+    }
+}
+
+/// Run a function once on a separate thread and wait at most `timeout` for it to finish.
+/// A function that times out keeps running in the background until the process exits, and slows down and adds to the
+/// peak memory of whatever runs after it.
+fn run_with_timeout<I, T>(
+    func: impl Fn(I) -> T + Send + 'static,
+    input: I,
+    timeout: Duration,
+) -> Result<(T, Duration, Option<alloc::Stats>), RecvTimeoutError>
+where
+    I: Send + 'static,
+    T: Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        let _ = sender.send(run_once(&func, input));
+    });
+
+    receiver.recv_timeout(timeout)
+}
+
+/// The part selected with `--part <n>`, the other part is skipped. Used by the bench harness.
+fn get_selected_part() -> Option<u8> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--part")? + 1;
+
+    match args.get(index).and_then(|x| x.parse::<u8>().ok()) {
+        Some(part) if part == 1 || part == 2 => Some(part),
+        _ => {
+            eprintln!("Unexpected command-line input. Format: cargo solve 1 --part 2");
+            process::exit(1);
+        }
+    }
+}
+
+/// Parse the `--timeout <seconds>` argument passed to `solve` or `all`.
+fn get_timeout() -> Option<Duration> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--timeout")? + 1;
+
+    match args.get(index).and_then(|x| x.parse::<f64>().ok()) {
+        Some(seconds) if seconds > 0.0 => Some(Duration::from_secs_f64(seconds)),
+        _ => {
+            eprintln!("Unexpected command-line input. Format: cargo solve 1 --timeout 10");
+            process::exit(1);
+        }
+    }
+}
+
+/// Print the peak resident memory of this process, as reported by the kernel in `/proc/self/status`.
+pub fn print_peak_memory() {
+    if let Some(kib) = peak_memory_kib() {
+        println!("Memory: {} peak", format_bytes(kib * 1024));
+    }
+}
 
-    (result, run.0, run.1)
+fn peak_memory_kib() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    status
+        .lines()
+        .find_map(|l| l.strip_prefix("VmHWM:"))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse()
+        .ok()
+}
+
+fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        b if b >= 1024 * 1024 => format!("{:.1}MiB", b as f64 / (1024.0 * 1024.0)),
+        b if b >= 1024 => format!("{:.1}KiB", b as f64 / 1024.0),
+        b => format!("{}B", b),
+    }
+}
+
+fn print_allocations(allocations: &Option<alloc::Stats>) {
+    if let Some(stats) = allocations {
+        println!(
+            "  ↳ {} allocations, {} allocated, {} peak",
+            stats.allocations,
+            format_bytes(stats.bytes as u64),
+            format_bytes(stats.peak_bytes as u64)
+        );
+    }
+}
+
+/// Print the spans recorded during the first run as an indented breakdown.
+fn print_spans() {
+    for record in spans::take() {
+        let indent = "  ".repeat(record.path.len());
+        let name = record.path.last().unwrap_or(&"");
+
+        if record.calls == 1 {
+            println!("{}↳ {}: {:.1?}", indent, name, record.total);
+        } else {
+            println!(
+                "{}↳ {}: {:.1?} ({} calls, {:.1?} each)",
+                indent,
+                name,
+                record.total,
+                record.calls,
+                record.total / record.calls.max(1) as u32
+            );
+        }
+    }
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+/// Name the frames recorded by the next run, see `template::viz`.
+fn label_frames(_label: &str) {
+    #[cfg(feature = "viz")]
+    viz::set_label(_label);
+}
+
+/// Print where the frames recorded during the first run were written.
+fn print_frames() {
+    #[cfg(feature = "viz")]
+    if let Some(summary) = viz::take_summary() {
+        println!("  ↳ {}", summary);
+    }
+}
+
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> Timing {
     let mut stdout = stdout();
 
     print!(" > {}benching{}", ANSI_ITALIC, ANSI_RESET);
@@ -75,31 +452,87 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations as u128,
-    )
+    Timing::from_samples(&mut timers)
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers.iter().map(|d| d.as_nanos()).sum::<u128>() / numbers.len() as u128
+/// Timing of a function over its bench samples. Without `--time`, the single run is the only sample.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Timing {
+    mean: Duration,
+    median: Duration,
+    /// Standard deviation of the samples.
+    spread: Duration,
+    samples: u128,
+}
+
+impl Timing {
+    fn from_samples(timers: &mut [Duration]) -> Self {
+        timers.sort_unstable();
+
+        let nanos: Vec<f64> = timers.iter().map(|d| d.as_nanos() as f64).collect();
+        let mean = nanos.iter().sum::<f64>() / nanos.len() as f64;
+        let variance = nanos.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / nanos.len() as f64;
+
+        Timing {
+            mean: Duration::from_nanos(mean as u64),
+            median: timers[timers.len() / 2],
+            spread: Duration::from_nanos(variance.sqrt() as u64),
+            samples: timers.len() as u128,
+        }
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({:.1?})", duration)
+/// Format a timing as ` (mean @ n samples) [median m ± spread]`, or ` (duration)` for a single run.
+/// `all` parses these, see `child_commands::parse_exec_time`.
+fn format_duration(timing: &Timing) -> String {
+    if timing.samples == 1 {
+        format!(" ({:.1?})", timing.mean)
     } else {
-        format!(" ({:.1?} @ {} samples)", duration, samples)
+        format!(
+            " ({:.1?} @ {} samples) [median {:.1?} ± {:.1?}]",
+            timing.mean, timing.samples, timing.median, timing.spread
+        )
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+/// Compare a result with the stored answer for its part and format the outcome for display.
+/// Parts without a stored answer are not marked.
+fn check_result<T: Display>(result: &T, day: u8, part: u8) -> String {
+    // stored answers are only valid for the real input and the default params.
+    if input::is_overridden() || env::args().any(|arg| arg == "--param") {
+        return String::new();
+    }
+
+    let answers = match answers::read(day) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("{}", e);
+            return String::new();
+        }
+    };
+
+    match answers.check(part, &result.to_string()) {
+        Check::Correct => format!(" {}", Check::Correct.symbol()),
+        Check::Incorrect => format!(
+            " {} expected {}",
+            Check::Incorrect.symbol(),
+            answers.get(part).unwrap_or_default()
+        ),
+        Check::Unknown => String::new(),
+    }
+}
+
+fn print_result<T: Display>(result: &Option<T>, part: &str, check: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
         Some(result) => {
             if result.to_string().contains('\n') {
-                let str = format!("{}: ▼ {}", part, duration_str);
+                let letters = match ocr::read_str(&result.to_string()) {
+                    Some(letters) => format!("{}{}{} ", ANSI_BOLD, letters, ANSI_RESET),
+                    None => String::new(),
+                };
+                let str = format!("{}: {}▼{} {}", part, letters, check, duration_str);
                 if is_intermediate_result {
                     print!("{}", str);
                 } else {
@@ -109,8 +542,8 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             } else {
                 let str = format!(
-                    "{}: {}{}{}{}",
-                    part, ANSI_BOLD, result, ANSI_RESET, duration_str
+                    "{}: {}{}{}{}{}",
+                    part, ANSI_BOLD, result, ANSI_RESET, check, duration_str
                 );
                 if is_intermediate_result {
                     print!("{}", str);
@@ -131,18 +564,15 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: u8,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCliError>> {
+/// Parse the arguments passed to `solve` and try to submit one part of the solution.
+/// Answers ruled out by earlier submissions in `data/submissions` are refused, every reply is recorded there,
+/// and an accepted answer is stored in `data/answers` so later runs can check against it. The part's example tests
+/// have to pass first, unless `--force` is passed.
+fn submit_result<T: Display>(result: T, day: u8, part: u8) {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
-        return None;
+        return;
     }
 
     if args.len() < 3 {
@@ -159,15 +589,135 @@ fn submit_result<T: Display>(
         }
     };
 
+    if input::is_overridden() {
+        eprintln!(
+            "Refusing to submit an answer computed from a replaced input (--input or AOC_DATA_DIR)."
+        );
+        process::exit(1);
+    }
+
+    if args.iter().any(|arg| arg == "--param") {
+        eprintln!("Refusing to submit an answer computed with changed params (--param).");
+        process::exit(1);
+    }
+
     if part_submit != part {
-        return None;
+        return;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    let result = result.to_string();
+
+    let mut ledger = match submissions::read(day) {
+        Ok(ledger) => ledger,
+        Err(e) => {
+            eprintln!(
+                "Failed to read \"{}\": {}",
+                submissions::get_submissions_path(day).display(),
+                e
+            );
+            process::exit(1);
+        }
+    };
+
+    // the server rate limits submissions across days, not per day.
+    let wait_until = match submissions::wait_until() {
+        Ok(wait_until) => wait_until,
+        Err(e) => {
+            eprintln!("Failed to read earlier submissions: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let now = submissions::now();
+    if let Err(refusal) =
+        submissions::check_cooldown(wait_until, now).and_then(|_| ledger.check(part, &result, now))
+    {
+        eprintln!("Refusing to submit {}: {}", result, refusal);
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    if args.contains(&"--force".into()) {
+        println!("Skipping example tests (--force).");
+    } else if let Err(e) = check_examples(day, part, !cfg!(debug_assertions)) {
+        eprintln!("Not submitting part {}: {}", part, e);
+        eprintln!("Pass --force to submit anyway.");
+        process::exit(1);
+    }
+
+    let client = match aoc_client::Client::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Failed to submit: {}", e);
+            process::exit(1);
+        }
+    };
+
+    println!("Submitting result...");
+
+    match client.submit(day, part, &result) {
+        Ok(reply) => {
+            println!("{}", reply.outcome);
+
+            ledger.record(part, &result, &reply, submissions::now());
+            if let Err(e) = submissions::write(day, &ledger) {
+                eprintln!(
+                    "Failed to record submission in \"{}\": {}",
+                    submissions::get_submissions_path(day).display(),
+                    e
+                );
+            }
+
+            if reply.outcome == aoc_client::Outcome::Correct {
+                if let Err(e) = answers::write(day, part, &result) {
+                    eprintln!(
+                        "Failed to store answer in \"{}\": {}",
+                        answers::get_answers_path(day).display(),
+                        e
+                    );
+                }
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to submit: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_comparison, format_duration, Timing};
+    use std::time::Duration;
+
+    #[test]
+    fn summarizes_samples() {
+        let mut timers: Vec<Duration> = [4, 1, 3, 2, 10].map(Duration::from_micros).to_vec();
+        let timing = Timing::from_samples(&mut timers);
+
+        assert_eq!(timing.mean, Duration::from_micros(4));
+        assert_eq!(timing.median, Duration::from_micros(3));
+        assert_eq!(timing.samples, 5);
+        assert_eq!(
+            format_duration(&timing),
+            " (4.0µs @ 5 samples) [median 3.0µs ± 3.2µs]"
+        );
+    }
+
+    #[test]
+    fn compares_variants() {
+        let timing = |mean: u64| Timing {
+            mean: Duration::from_micros(mean),
+            median: Duration::from_micros(mean),
+            spread: Duration::from_micros(1),
+            samples: 10,
+        };
+
+        assert_eq!(
+            format_comparison(&[("part_one", timing(4)), ("part_one_vec", timing(10))]),
+            vec![
+                "  part_one          4.0µs  median     4.0µs ± 1.0µs      1.00×",
+                "  part_one_vec     10.0µs  median    10.0µs ± 1.0µs      2.50×",
+            ]
+        );
+    }
 }
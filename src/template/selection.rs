@@ -0,0 +1,173 @@
+/// Selects which days `all` runs: `--days 1-10,15,20-`, `--skip 16,19`, `--only-failing` and `--slow-threshold <ms>`.
+/// The outcome of every day that ran is stored in `data/status.toml`, which is what `--only-failing` reads.
+use std::{fs, io, path::PathBuf};
+
+use crate::template::{bench_history, input, toml};
+
+pub fn get_status_path() -> PathBuf {
+    input::get_data_dir().join("status.toml")
+}
+
+/// Parse a list of days such as `1-10,15,20-`. Open ranges extend to day 1 or day 25.
+pub fn parse_days(spec: &str) -> Result<Vec<usize>, String> {
+    let mut days = vec![];
+
+    for item in spec.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()) {
+        let parse_day = |s: &str, default: usize| -> Result<usize, String> {
+            if s.is_empty() {
+                return Ok(default);
+            }
+            match s.trim().parse() {
+                Ok(day) if (1..=25).contains(&day) => Ok(day),
+                _ => Err(format!("invalid day \"{}\" in \"{}\".", s, spec)),
+            }
+        };
+
+        let (start, end) = match item.split_once('-') {
+            Some((start, end)) => (parse_day(start, 1)?, parse_day(end, 25)?),
+            None => {
+                let day = parse_day(item, 0)?;
+                (day, day)
+            }
+        };
+
+        if start > end {
+            return Err(format!("empty range \"{}\" in \"{}\".", item, spec));
+        }
+
+        days.extend(start..=end);
+    }
+
+    days.sort_unstable();
+    days.dedup();
+    Ok(days)
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Selection {
+    pub days: Option<Vec<usize>>,
+    pub skip: Vec<usize>,
+    pub only_failing: bool,
+    /// Skip days whose last recorded run took longer than this many milliseconds.
+    pub slow_threshold: Option<f64>,
+}
+
+impl Selection {
+    /// Whether every day is selected, i.e. results can be used for the README.
+    pub fn is_everything(&self) -> bool {
+        self.days.is_none()
+            && self.skip.is_empty()
+            && !self.only_failing
+            && self.slow_threshold.is_none()
+    }
+
+    /// Resolve the selection into a list of days. Prints a note for every day that is excluded based on past runs.
+    pub fn resolve(&self, is_release: bool) -> Vec<usize> {
+        let mut days: Vec<usize> = match &self.days {
+            Some(days) => days.clone(),
+            None => (1..=25).collect(),
+        };
+
+        days.retain(|day| !self.skip.contains(day));
+
+        if self.only_failing {
+            let passing = read_status().unwrap_or_else(|e| {
+                eprintln!(
+                    "Failed to read \"{}\": {:?}",
+                    get_status_path().display(),
+                    e
+                );
+                vec![]
+            });
+            days.retain(|day| !passing.contains(&(*day, true)));
+        }
+
+        if let Some(threshold) = self.slow_threshold {
+            let profile = if is_release { "release" } else { "debug" };
+            let runs = bench_history::read().unwrap_or_default();
+
+            if let Some(run) = runs.iter().rev().find(|r| r.env.profile == profile) {
+                days.retain(|day| {
+                    let millis = run.day_total(*day) / 1000000_f64;
+                    if millis > threshold {
+                        println!(
+                            "Skipping day {} ({:.2}ms in run {}).",
+                            day, millis, run.timestamp
+                        );
+                        false
+                    } else {
+                        true
+                    }
+                });
+            } else {
+                eprintln!(
+                    "No {} run in the benchmark history, --slow-threshold has no effect.",
+                    profile
+                );
+            }
+        }
+
+        days
+    }
+}
+
+/// Read the outcome of the last run of each day: `(day, passed)`.
+pub fn read_status() -> Result<Vec<(usize, bool)>, io::Error> {
+    let s = match fs::read_to_string(get_status_path()) {
+        Ok(s) => s,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e),
+    };
+
+    let doc =
+        toml::parse(&s).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    Ok(doc
+        .root()
+        .map(|root| {
+            root.entries
+                .iter()
+                .filter_map(|(day, status)| Some((day.parse().ok()?, status == "pass")))
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// Merge the outcome of the days that just ran into the stored status.
+pub fn update_status(results: &[(usize, bool)]) -> Result<(), io::Error> {
+    let mut status = read_status()?;
+
+    for &(day, passed) in results {
+        status.retain(|(d, _)| *d != day);
+        status.push((day, passed));
+    }
+
+    status.sort_unstable();
+
+    let content: String = status
+        .iter()
+        .map(|(day, passed)| format!("{} = \"{}\"\n", day, if *passed { "pass" } else { "fail" }))
+        .collect();
+
+    fs::write(get_status_path(), content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_days;
+
+    #[test]
+    fn parses_day_lists() {
+        assert_eq!(parse_days("1-3,15,24-").unwrap(), vec![1, 2, 3, 15, 24, 25]);
+        assert_eq!(parse_days("-2, 2").unwrap(), vec![1, 2]);
+        assert_eq!(parse_days("16,19").unwrap(), vec![16, 19]);
+    }
+
+    #[test]
+    fn rejects_invalid_days() {
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("26").is_err());
+        assert!(parse_days("5-3").is_err());
+        assert!(parse_days("a").is_err());
+    }
+}
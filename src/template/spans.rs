@@ -0,0 +1,127 @@
+/// Timing spans. `let _s = advent_of_code::span!("floyd");` measures the rest of the enclosing block as a sub-phase
+/// of the parser or part that is running. Spans nest and calls with the same path are summed up. They are only
+/// recorded when `--spans` is passed, and only for the first run of the parser and each part, not while benching.
+use std::{
+    cell::RefCell,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+static RECORDING: AtomicBool = AtomicBool::new(false);
+static RECORDS: Mutex<Vec<Record>> = Mutex::new(Vec::new());
+
+thread_local! {
+    /// Names of the spans open on this thread. Spans on worker threads start a new path.
+    static STACK: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    /// Names of the enclosing spans and this span.
+    pub path: Vec<&'static str>,
+    pub total: Duration,
+    pub calls: usize,
+}
+
+/// Created by `span!`, records the time until it is dropped.
+pub struct Span {
+    start: Option<Instant>,
+}
+
+pub fn enter(name: &'static str) -> Span {
+    if !RECORDING.load(Ordering::Relaxed) {
+        return Span { start: None };
+    }
+
+    let path = STACK.with(|stack| {
+        let mut stack = stack.borrow_mut();
+        stack.push(name);
+        stack.clone()
+    });
+
+    // register on entry, so that spans are listed before the spans nested in them.
+    let mut records = RECORDS.lock().unwrap();
+    if !records.iter().any(|r| r.path == path) {
+        records.push(Record {
+            path,
+            total: Duration::ZERO,
+            calls: 0,
+        });
+    }
+
+    Span {
+        start: Some(Instant::now()),
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let start = match self.start {
+            Some(start) => start,
+            None => return,
+        };
+        let elapsed = start.elapsed();
+
+        let path = STACK.with(|stack| {
+            let mut stack = stack.borrow_mut();
+            let path = stack.clone();
+            stack.pop();
+            path
+        });
+
+        if let Some(record) = RECORDS.lock().unwrap().iter_mut().find(|r| r.path == path) {
+            record.total += elapsed;
+            record.calls += 1;
+        }
+    }
+}
+
+/// Start recording if `--spans` was passed, discarding earlier records.
+pub fn start() {
+    if std::env::args().any(|x| x == "--spans") {
+        RECORDS.lock().unwrap().clear();
+        RECORDING.store(true, Ordering::Relaxed);
+    }
+}
+
+pub fn stop() {
+    RECORDING.store(false, Ordering::Relaxed);
+}
+
+/// Take the records since the last `start`.
+pub fn take() -> Vec<Record> {
+    std::mem::take(&mut *RECORDS.lock().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{enter, take, Record, RECORDING};
+    use std::sync::atomic::Ordering;
+
+    #[test]
+    fn records_nested_spans() {
+        RECORDING.store(true, Ordering::Relaxed);
+        {
+            let _outer = enter("outer");
+            for _ in 0..3 {
+                let _inner = enter("inner");
+            }
+        }
+        RECORDING.store(false, Ordering::Relaxed);
+        let _ignored = enter("ignored");
+
+        let records = take();
+        let summary: Vec<(Vec<&str>, usize)> = records
+            .iter()
+            .map(|Record { path, calls, .. }| (path.clone(), *calls))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![(vec!["outer"], 1), (vec!["outer", "inner"], 3)]
+        );
+        assert!(records[0].total >= records[1].total);
+    }
+}
@@ -0,0 +1,374 @@
+/// Ledger of every answer submitted for a day, stored in `data/submissions/DD.json`:
+///
+/// ```json
+/// [
+///   {"timestamp": 1670900000, "part": 1, "answer": "1234", "outcome": "too_high", "cooldown": 60},
+///   {"timestamp": 1670900120, "part": 1, "answer": "1000", "outcome": "correct"}
+/// ]
+/// ```
+///
+/// Before submitting, the runner checks the ledger so that answers which are known to be wrong are never sent.
+/// Advent of Code rate limits submissions across all days, so a cooldown recorded in any day's ledger applies to all.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs, io,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{
+    aoc_client::{Outcome, Reply},
+    input,
+};
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "malformed submissions file: {}", e),
+            Error::IO(e) => write!(f, "could not access submissions file: {}", e),
+        }
+    }
+}
+
+/// Reason for not sending an answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadyRejected { timestamp: u64, outcome: Outcome },
+    NotBelowTooHigh(String),
+    NotAboveTooLow(String),
+    Cooldown { remaining: u64 },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadyRejected { timestamp, outcome } => write!(
+                f,
+                "this answer was already rejected at {} ({}).",
+                timestamp,
+                describe(outcome)
+            ),
+            Refusal::NotBelowTooHigh(bound) => {
+                write!(f, "{} was too high, the answer has to be lower.", bound)
+            }
+            Refusal::NotAboveTooLow(bound) => {
+                write!(f, "{} was too low, the answer has to be higher.", bound)
+            }
+            Refusal::Cooldown { remaining } => write!(
+                f,
+                "the last submission, of any day, asked to wait, {}s left.",
+                remaining
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub timestamp: u64,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Seconds the server asked to wait before the next submission.
+    pub cooldown: Option<u64>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Ledger {
+    pub entries: Vec<Entry>,
+}
+
+impl Ledger {
+    /// The time until which the server asked to wait after the submissions in this ledger.
+    pub fn wait_until(&self) -> Option<u64> {
+        self.entries
+            .iter()
+            .filter_map(|e| Some(e.timestamp + e.cooldown?))
+            .max()
+    }
+
+    /// Check an answer against earlier submissions. `now` is a unix timestamp in seconds.
+    pub fn check(&self, part: u8, answer: &str, now: u64) -> Result<(), Refusal> {
+        check_cooldown(self.wait_until(), now)?;
+
+        let entries: Vec<&Entry> = self.entries.iter().filter(|e| e.part == part).collect();
+
+        if let Some(entry) = entries
+            .iter()
+            .find(|e| e.answer == answer && is_rejection(&e.outcome))
+        {
+            return Err(Refusal::AlreadyRejected {
+                timestamp: entry.timestamp,
+                outcome: entry.outcome.clone(),
+            });
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            let bound = |outcome: Outcome| {
+                entries
+                    .iter()
+                    .filter(move |e| e.outcome == outcome)
+                    .filter_map(|e| e.answer.parse::<i128>().ok())
+            };
+
+            if let Some(too_high) = bound(Outcome::TooHigh).min() {
+                if value >= too_high {
+                    return Err(Refusal::NotBelowTooHigh(too_high.to_string()));
+                }
+            }
+
+            if let Some(too_low) = bound(Outcome::TooLow).max() {
+                if value <= too_low {
+                    return Err(Refusal::NotAboveTooLow(too_low.to_string()));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn record(&mut self, part: u8, answer: &str, reply: &Reply, now: u64) {
+        self.entries.push(Entry {
+            timestamp: now,
+            part,
+            answer: answer.to_string(),
+            outcome: reply.outcome.clone(),
+            cooldown: reply.cooldown,
+        });
+    }
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let err = |message: &str| Error::Parser(message.to_string());
+
+        let value: JsonValue = s.parse().map_err(|e| Error::Parser(format!("{}", e)))?;
+        let items: &Vec<JsonValue> = value.get().ok_or_else(|| err("expected an array"))?;
+
+        let entries = items
+            .iter()
+            .map(|item| {
+                let object: &HashMap<String, JsonValue> =
+                    item.get().ok_or_else(|| err("expected an object"))?;
+                let number =
+                    |key: &str| -> Option<u64> { object.get(key)?.get::<f64>().map(|n| *n as u64) };
+                let string =
+                    |key: &str| -> Option<String> { object.get(key)?.get::<String>().cloned() };
+
+                let cooldown = number("cooldown");
+                let outcome = match string("outcome").as_deref() {
+                    Some("correct") => Outcome::Correct,
+                    Some("too_high") => Outcome::TooHigh,
+                    Some("too_low") => Outcome::TooLow,
+                    Some("incorrect") => Outcome::Incorrect,
+                    Some("wait") => Outcome::Wait(cooldown.unwrap_or_default()),
+                    Some("already_solved") => Outcome::AlreadySolved,
+                    Some("unknown") => Outcome::Unknown(string("message").unwrap_or_default()),
+                    _ => return Err(err("missing or unknown outcome")),
+                };
+
+                Ok(Entry {
+                    timestamp: number("timestamp").ok_or_else(|| err("missing timestamp"))?,
+                    part: number("part").ok_or_else(|| err("missing part"))? as u8,
+                    answer: string("answer").ok_or_else(|| err("missing answer"))?,
+                    outcome,
+                    cooldown,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Ledger { entries })
+    }
+
+    /// Serialize with one entry per line and a fixed key order, so the file diffs nicely.
+    fn to_json(&self) -> String {
+        let quote = |s: &str| JsonValue::String(s.to_string()).stringify().unwrap();
+
+        let lines: Vec<String> = self
+            .entries
+            .iter()
+            .map(|e| {
+                let (outcome, message) = match &e.outcome {
+                    Outcome::Correct => ("correct", None),
+                    Outcome::TooHigh => ("too_high", None),
+                    Outcome::TooLow => ("too_low", None),
+                    Outcome::Incorrect => ("incorrect", None),
+                    Outcome::Wait(_) => ("wait", None),
+                    Outcome::AlreadySolved => ("already_solved", None),
+                    Outcome::Unknown(message) => ("unknown", Some(message)),
+                };
+
+                let mut line = format!(
+                    "  {{\"timestamp\": {}, \"part\": {}, \"answer\": {}, \"outcome\": {}",
+                    e.timestamp,
+                    e.part,
+                    quote(&e.answer),
+                    quote(outcome)
+                );
+                if let Some(cooldown) = e.cooldown {
+                    line.push_str(&format!(", \"cooldown\": {}", cooldown));
+                }
+                if let Some(message) = message {
+                    line.push_str(&format!(", \"message\": {}", quote(message)));
+                }
+                line.push('}');
+                line
+            })
+            .collect();
+
+        format!("[\n{}\n]\n", lines.join(",\n"))
+    }
+}
+
+/// Refuse to submit before `wait_until`.
+pub fn check_cooldown(wait_until: Option<u64>, now: u64) -> Result<(), Refusal> {
+    match wait_until {
+        Some(wait_until) if wait_until > now => Err(Refusal::Cooldown {
+            remaining: wait_until - now,
+        }),
+        _ => Ok(()),
+    }
+}
+
+fn is_rejection(outcome: &Outcome) -> bool {
+    matches!(
+        outcome,
+        Outcome::TooHigh | Outcome::TooLow | Outcome::Incorrect
+    )
+}
+
+fn describe(outcome: &Outcome) -> &'static str {
+    match outcome {
+        Outcome::TooHigh => "too high",
+        Outcome::TooLow => "too low",
+        _ => "incorrect",
+    }
+}
+
+pub fn get_submissions_path(day: u8) -> PathBuf {
+    input::get_day_path("submissions", day, "json")
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Read the ledger for a day. A missing file means nothing was submitted yet.
+pub fn read(day: u8) -> Result<Ledger, Error> {
+    match fs::read_to_string(get_submissions_path(day)) {
+        Ok(s) => Ledger::from_str(&s),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Ledger::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// The latest cooldown over the ledgers of all days.
+pub fn wait_until() -> Result<Option<u64>, Error> {
+    (1..=25).try_fold(None, |wait_until, day| {
+        Ok(wait_until.max(read(day)?.wait_until()))
+    })
+}
+
+pub fn write(day: u8, ledger: &Ledger) -> Result<(), Error> {
+    let path = get_submissions_path(day);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, ledger.to_json())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_cooldown, Ledger, Refusal};
+    use crate::template::aoc_client::{Outcome, Reply};
+
+    fn reply(outcome: Outcome, cooldown: Option<u64>) -> Reply {
+        Reply { outcome, cooldown }
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let mut ledger = Ledger::default();
+        ledger.record(1, "500", &reply(Outcome::TooHigh, Some(60)), 1000);
+        ledger.record(1, "100", &reply(Outcome::TooLow, Some(60)), 1100);
+        ledger.record(2, "abc", &reply(Outcome::Incorrect, Some(60)), 1200);
+
+        assert_eq!(
+            ledger.check(1, "300", 1230),
+            Err(Refusal::Cooldown { remaining: 30 })
+        );
+        assert_eq!(
+            ledger.check(1, "500", 2000),
+            Err(Refusal::AlreadyRejected {
+                timestamp: 1000,
+                outcome: Outcome::TooHigh
+            })
+        );
+        assert_eq!(
+            ledger.check(1, "600", 2000),
+            Err(Refusal::NotBelowTooHigh("500".into()))
+        );
+        assert_eq!(
+            ledger.check(1, "50", 2000),
+            Err(Refusal::NotAboveTooLow("100".into()))
+        );
+        assert!(matches!(
+            ledger.check(2, "abc", 2000),
+            Err(Refusal::AlreadyRejected { .. })
+        ));
+        assert_eq!(ledger.check(1, "300", 2000), Ok(()));
+        assert_eq!(ledger.check(2, "300", 2000), Ok(()));
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let mut ledger = Ledger::default();
+        ledger.record(1, "500", &reply(Outcome::TooHigh, Some(60)), 1000);
+        ledger.record(
+            1,
+            "5\"0",
+            &reply(Outcome::Unknown("huh".into()), None),
+            1100,
+        );
+        ledger.record(2, "42", &reply(Outcome::Wait(37), Some(37)), 1200);
+
+        let json = ledger.to_json();
+        assert!(json.starts_with(
+            "[\n  {\"timestamp\": 1000, \"part\": 1, \"answer\": \"500\", \"outcome\": \"too_high\", \"cooldown\": 60},\n"
+        ));
+        assert_eq!(Ledger::from_str(&json).unwrap(), ledger);
+    }
+
+    #[test]
+    fn waits_for_the_latest_cooldown() {
+        let mut day_one = Ledger::default();
+        day_one.record(1, "500", &reply(Outcome::TooHigh, Some(60)), 1000);
+        let mut day_two = Ledger::default();
+        day_two.record(1, "42", &reply(Outcome::Wait(300), Some(300)), 900);
+
+        let wait_until = day_one.wait_until().max(day_two.wait_until());
+        assert_eq!(
+            check_cooldown(wait_until, 1100),
+            Err(Refusal::Cooldown { remaining: 100 })
+        );
+        assert_eq!(check_cooldown(wait_until, 1200), Ok(()));
+        assert_eq!(check_cooldown(Ledger::default().wait_until(), 0), Ok(()));
+    }
+}
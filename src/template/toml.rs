@@ -0,0 +1,191 @@
+/// Reader for the small subset of TOML used by the files under `data/`.
+/// Supports `[section]` headers, `key = value` pairs, `#` comments, basic strings with escapes and bare values.
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Error {
+    pub line: usize,
+    pub message: String,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// A named table of key/value pairs. The root table has an empty name.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Table {
+    pub name: String,
+    pub entries: Vec<(String, String)>,
+}
+
+impl Table {
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// A parsed document: the root table followed by each `[section]` in file order.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Document {
+    pub tables: Vec<Table>,
+}
+
+impl Document {
+    pub fn root(&self) -> Option<&Table> {
+        self.table("")
+    }
+
+    pub fn table(&self, name: &str) -> Option<&Table> {
+        self.tables.iter().find(|t| t.name == name)
+    }
+
+    /// Sections other than the root table.
+    pub fn sections(&self) -> impl Iterator<Item = &Table> {
+        self.tables.iter().filter(|t| !t.name.is_empty())
+    }
+}
+
+pub fn parse(s: &str) -> Result<Document, Error> {
+    let mut tables = vec![Table::default()];
+
+    for (i, raw_line) in s.lines().enumerate() {
+        let line_no = i + 1;
+        let line = strip_comment(raw_line).trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[') {
+            let name = name.strip_suffix(']').ok_or_else(|| Error {
+                line: line_no,
+                message: "unterminated section header.".into(),
+            })?;
+            tables.push(Table {
+                name: name.trim().to_string(),
+                entries: vec![],
+            });
+            continue;
+        }
+
+        let (key, value) = line.split_once('=').ok_or_else(|| Error {
+            line: line_no,
+            message: format!("expected `key = value`, found `{}`.", line),
+        })?;
+
+        let value = parse_value(value.trim()).map_err(|message| Error {
+            line: line_no,
+            message,
+        })?;
+
+        tables
+            .last_mut()
+            .unwrap()
+            .entries
+            .push((key.trim().to_string(), value));
+    }
+
+    Ok(Document { tables })
+}
+
+/// Format a value so that `parse` reads it back unchanged.
+/// Integers are written bare, everything else becomes a basic string.
+pub fn format_value(value: &str) -> String {
+    if !value.is_empty() && value.parse::<i64>().is_ok() {
+        return value.to_string();
+    }
+
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+
+    line
+}
+
+fn parse_value(value: &str) -> Result<String, String> {
+    let inner = match value.strip_prefix('"') {
+        Some(rest) => rest
+            .strip_suffix('"')
+            .ok_or_else(|| "unterminated string.".to_string())?,
+        None if value.is_empty() => return Err("missing value.".into()),
+        None => return Ok(value.to_string()),
+    };
+
+    let mut out = String::new();
+    let mut chars = inner.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('"') => out.push('"'),
+            Some('\\') => out.push('\\'),
+            Some(c) => return Err(format!("unsupported escape `\\{}`.", c)),
+            None => return Err("unterminated escape.".into()),
+        }
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_value, parse};
+
+    #[test]
+    fn parses_root_and_sections() {
+        let doc = parse("a = 1\n# comment\n\n[larger]\nb = \"x # y\" # trailing\n").unwrap();
+        assert_eq!(doc.root().unwrap().get("a"), Some("1"));
+        assert_eq!(doc.table("larger").unwrap().get("b"), Some("x # y"));
+        assert_eq!(doc.sections().count(), 1);
+    }
+
+    #[test]
+    fn round_trips_values() {
+        for value in ["72511", "EFGERURE", "a \"b\"\nc\\d", "-3"] {
+            let doc = parse(&format!("v = {}", format_value(value))).unwrap();
+            assert_eq!(doc.root().unwrap().get("v"), Some(value));
+        }
+    }
+
+    #[test]
+    fn reports_line_of_error() {
+        let err = parse("a = 1\nb\n").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(parse("a = \"open").is_err());
+    }
+}
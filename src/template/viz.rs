@@ -0,0 +1,542 @@
+/// Frame-by-frame visualisation of simulations, compiled in with the `viz` feature. A solution emits frames with
+/// `advent_of_code::viz!(frame)`, which only builds the frame when `--viz <ansi|ppm|gif>` was passed and expands to
+/// nothing without the feature, so benchmarks are unaffected. Like spans, frames are only recorded during the first
+/// run of the parser and each part. They are drawn as an animation in the terminal (`ansi`), or written to
+/// `data/viz/<day>/` as one PPM image per frame (`ppm`) or as an animated GIF per part (`gif`).
+use std::{
+    collections::HashMap,
+    env, fs, io,
+    path::PathBuf,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use crate::template::input;
+use crate::{Point, SimpleGrid, SparseGrid};
+
+pub type Color = [u8; 3];
+
+/// Every cell is drawn as a square of this many pixels in images.
+const SCALE: usize = 4;
+/// Time between two frames.
+const DELAY: Duration = Duration::from_millis(50);
+
+static RECORDING: AtomicBool = AtomicBool::new(false);
+static STATE: Mutex<State> = Mutex::new(State {
+    label: String::new(),
+    frames: Vec::new(),
+    count: 0,
+    summary: None,
+});
+
+struct State {
+    /// Name of the parser or part that is running, e.g. `part_1`.
+    label: String,
+    /// Frames buffered for a GIF.
+    frames: Vec<Frame>,
+    count: usize,
+    /// Where the frames of the last run went, printed after its result.
+    summary: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Ansi,
+    Ppm,
+    Gif,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ansi" => Ok(Format::Ansi),
+            "ppm" => Ok(Format::Ppm),
+            "gif" => Ok(Format::Gif),
+            _ => Err(format!(
+                "unknown viz format \"{}\", expected ansi, ppm or gif.",
+                s
+            )),
+        }
+    }
+}
+
+/// An image of a grid. Each cell holds an index into the palette. All frames of a GIF use the palette of its first.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    pub width: usize,
+    pub height: usize,
+    cells: Vec<u8>,
+    palette: &'static [Color],
+}
+
+impl Frame {
+    /// A frame filled with the first color of the palette.
+    pub fn new(width: usize, height: usize, palette: &'static [Color]) -> Self {
+        assert!(
+            !palette.is_empty() && palette.len() <= 256,
+            "a palette has 1 to 256 colors."
+        );
+
+        Frame {
+            width,
+            height,
+            cells: vec![0; width * height],
+            palette,
+        }
+    }
+
+    /// A frame with the color of each cell given by `color(x, y)`, e.g. to draw a bitboard.
+    pub fn from_fn(
+        width: usize,
+        height: usize,
+        palette: &'static [Color],
+        color: impl Fn(usize, usize) -> u8,
+    ) -> Self {
+        let mut frame = Frame::new(width, height, palette);
+        for y in 0..height {
+            for x in 0..width {
+                frame.set(x, y, color(x, y));
+            }
+        }
+        frame
+    }
+
+    pub fn from_grid<T>(
+        grid: &SimpleGrid<T>,
+        palette: &'static [Color],
+        color: impl Fn(&T) -> u8,
+    ) -> Self {
+        Frame::from_fn(grid.width, grid.height, palette, |x, y| {
+            color(&grid.data[y][x])
+        })
+    }
+
+    /// A frame of the points of a sparse grid between `min` and `max`, inclusive. Points without a value are passed
+    /// as `None`. The bounds are explicit so that frames of a growing grid line up.
+    pub fn from_sparse<T>(
+        grid: &SparseGrid<T>,
+        min: &Point,
+        max: &Point,
+        palette: &'static [Color],
+        color: impl Fn(Option<&T>) -> u8,
+    ) -> Self {
+        let width = (max.x - min.x + 1).max(0) as usize;
+        let height = (max.y - min.y + 1).max(0) as usize;
+
+        Frame::from_fn(width, height, palette, |x, y| {
+            color(grid.get(&Point {
+                x: min.x + x as isize,
+                y: min.y + y as isize,
+            }))
+        })
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> u8 {
+        self.cells[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, color: u8) {
+        assert!(
+            (color as usize) < self.palette.len(),
+            "color {} is not in the palette.",
+            color
+        );
+        self.cells[y * self.width + x] = color;
+    }
+
+    fn rgb(&self, x: usize, y: usize) -> Color {
+        self.palette[self.get(x, y) as usize]
+    }
+}
+
+/// The format passed with `--viz`, if any. Exits for an unknown format.
+pub fn get_format() -> Option<Format> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--viz")?;
+
+    match args.get(index + 1).map(|x| x.parse()) {
+        Some(Ok(format)) => Some(format),
+        Some(Err(e)) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        None => {
+            eprintln!("--viz expects a format: ansi, ppm or gif.");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Name the frames of the parser or part that runs next.
+pub fn set_label(label: &str) {
+    STATE.lock().unwrap().label = label.to_lowercase().replace(' ', "_");
+}
+
+/// Start recording if `--viz` was passed.
+pub fn start() {
+    if get_format().is_some() {
+        let mut state = STATE.lock().unwrap();
+        state.frames.clear();
+        state.count = 0;
+        state.summary = None;
+        RECORDING.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Stop recording and write the GIF, if frames were recorded for one.
+pub fn stop() {
+    if !RECORDING.swap(false, Ordering::Relaxed) {
+        return;
+    }
+
+    let mut state = STATE.lock().unwrap();
+    if state.count == 0 {
+        return;
+    }
+
+    let result = match get_format() {
+        Some(Format::Gif) => {
+            let path = get_dir().join(format!("{}.gif", state.label));
+            let gif = encode_gif(&state.frames, SCALE, DELAY);
+            state.frames.clear();
+            write(&path, &gif).map(|_| path)
+        }
+        Some(Format::Ppm) => Ok(get_dir().join(&state.label)),
+        _ => return,
+    };
+
+    state.summary = Some(match result {
+        Ok(path) => format!("{} frame(s) in \"{}\"", state.count, path.display()),
+        Err(e) => format!("failed to write {} frame(s): {}", state.count, e),
+    });
+}
+
+/// Take the summary of the frames written since the last `start`.
+pub fn take_summary() -> Option<String> {
+    STATE.lock().unwrap().summary.take()
+}
+
+/// Record a frame. Used by `viz!`, which only builds the frame while recording.
+pub fn frame(build: impl FnOnce() -> Frame) {
+    if !RECORDING.load(Ordering::Relaxed) {
+        return;
+    }
+
+    let frame = build();
+    let mut state = STATE.lock().unwrap();
+    state.count += 1;
+
+    match get_format() {
+        Some(Format::Ansi) => {
+            // clear the screen before the first frame, then draw over it.
+            let clear = if state.count == 1 { "\x1b[2J" } else { "" };
+            print!("{}{}", clear, to_ansi(&frame));
+            thread::sleep(DELAY);
+        }
+        Some(Format::Ppm) => {
+            let path = get_dir()
+                .join(&state.label)
+                .join(format!("{:05}.ppm", state.count));
+            if let Err(e) = write(&path, &encode_ppm(&frame, SCALE)) {
+                eprintln!("Failed to write \"{}\": {}", path.display(), e);
+            }
+        }
+        Some(Format::Gif) => state.frames.push(frame),
+        None => {}
+    }
+}
+
+/// `data/viz/<name of the day's binary>`.
+fn get_dir() -> PathBuf {
+    let name = env::current_exe()
+        .ok()
+        .and_then(|exe| exe.file_stem().map(|s| s.to_string_lossy().to_string()))
+        .unwrap_or_default();
+    input::get_data_dir().join("viz").join(name)
+}
+
+fn write(path: &PathBuf, bytes: &[u8]) -> Result<(), io::Error> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, bytes)
+}
+
+/// Draw a frame from the top left corner of the terminal, two cells per character with `▀` in 24-bit color.
+fn to_ansi(frame: &Frame) -> String {
+    let mut s = String::from("\x1b[H");
+
+    for y in (0..frame.height).step_by(2) {
+        for x in 0..frame.width {
+            let [r, g, b] = frame.rgb(x, y);
+            s += &format!("\x1b[38;2;{};{};{}m", r, g, b);
+
+            match y + 1 < frame.height {
+                true => {
+                    let [r, g, b] = frame.rgb(x, y + 1);
+                    s += &format!("\x1b[48;2;{};{};{}m▀", r, g, b);
+                }
+                false => s += "\x1b[49m▀",
+            }
+        }
+        s += "\x1b[0m\n";
+    }
+
+    s
+}
+
+/// A binary PPM image of the frame, `scale` pixels per cell.
+fn encode_ppm(frame: &Frame, scale: usize) -> Vec<u8> {
+    let (width, height) = (frame.width * scale, frame.height * scale);
+    let mut bytes = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+
+    for y in 0..height {
+        for x in 0..width {
+            bytes.extend(frame.rgb(x / scale, y / scale));
+        }
+    }
+
+    bytes
+}
+
+/// An animated GIF of the frames, looping forever. Smaller frames are padded with the first color.
+fn encode_gif(frames: &[Frame], scale: usize, delay: Duration) -> Vec<u8> {
+    let palette = frames.first().map(|f| f.palette).unwrap_or(&[[0, 0, 0]]);
+    let width = frames.iter().map(|f| f.width).max().unwrap_or(0) * scale;
+    let height = frames.iter().map(|f| f.height).max().unwrap_or(0) * scale;
+
+    // the color table holds 2^bits colors, and LZW codes start at no less than 2 bits.
+    let bits = (palette.len().next_power_of_two().trailing_zeros() as u8).max(2);
+    let le = |n: usize| (n as u16).to_le_bytes();
+
+    let mut bytes = b"GIF89a".to_vec();
+    bytes.extend(le(width));
+    bytes.extend(le(height));
+    // global color table, 8 bits per primary, table size.
+    bytes.extend([0x80 | 0x70 | (bits - 1), 0, 0]);
+    for i in 0..1 << bits {
+        bytes.extend(palette.get(i).unwrap_or(&[0, 0, 0]));
+    }
+
+    // loop forever.
+    bytes.extend([0x21, 0xFF, 0x0B]);
+    bytes.extend(b"NETSCAPE2.0");
+    bytes.extend([0x03, 0x01, 0x00, 0x00, 0x00]);
+
+    for frame in frames {
+        // graphic control extension with the delay in hundredths of a second.
+        bytes.extend([0x21, 0xF9, 0x04, 0x00]);
+        bytes.extend(le(delay.as_millis() as usize / 10));
+        bytes.extend([0x00, 0x00]);
+
+        // image descriptor over the whole canvas, without a local color table.
+        bytes.extend([0x2C, 0, 0, 0, 0]);
+        bytes.extend(le(width));
+        bytes.extend(le(height));
+        bytes.push(0x00);
+
+        let mut indices = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let (x, y) = (x / scale, y / scale);
+                let inside = x < frame.width && y < frame.height;
+                indices.push(if inside { frame.get(x, y) } else { 0 });
+            }
+        }
+
+        bytes.push(bits);
+        for block in encode_lzw(&indices, bits).chunks(255) {
+            bytes.push(block.len() as u8);
+            bytes.extend(block);
+        }
+        bytes.push(0x00);
+    }
+
+    bytes.push(0x3B);
+    bytes
+}
+
+/// Compress color indices with the variable-length LZW of GIF.
+fn encode_lzw(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+
+    let mut bytes = vec![];
+    let mut buffer = 0u32;
+    let mut buffered = 0;
+    let mut emit = |code: u16, size: u8| {
+        buffer |= (code as u32) << buffered;
+        buffered += size;
+        while buffered >= 8 {
+            bytes.push(buffer as u8);
+            buffer >>= 8;
+            buffered -= 8;
+        }
+    };
+
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut code_size = min_code_size + 1;
+    let mut next = end + 1;
+    emit(clear, code_size);
+
+    if let Some((&first, rest)) = indices.split_first() {
+        let mut current = first as u16;
+
+        for &index in rest {
+            if let Some(&code) = table.get(&(current, index)) {
+                current = code;
+                continue;
+            }
+
+            emit(current, code_size);
+            if next < 4096 {
+                if next == 1 << code_size {
+                    code_size += 1;
+                }
+                table.insert((current, index), next);
+                next += 1;
+            } else {
+                // the table is full, start over.
+                emit(clear, code_size);
+                table.clear();
+                code_size = min_code_size + 1;
+                next = end + 1;
+            }
+            current = index as u16;
+        }
+
+        emit(current, code_size);
+    }
+
+    emit(end, code_size);
+    if buffered > 0 {
+        bytes.push(buffer as u8);
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encode_gif, encode_lzw, encode_ppm, Color, Frame};
+    use crate::{Point, SparseGrid};
+    use std::time::Duration;
+
+    static PALETTE: &[Color] = &[[0, 0, 0], [255, 255, 255], [200, 0, 0]];
+
+    /// A plain GIF LZW decoder, to check the encoder against.
+    fn decode_lzw(bytes: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let end = clear + 1;
+        let reset = || -> Vec<Vec<u8>> { (0..clear + 2).map(|i| vec![i as u8]).collect() };
+
+        let mut table = reset();
+        let mut code_size = min_code_size + 1;
+        let mut previous: Option<Vec<u8>> = None;
+        let mut output = vec![];
+        let mut bit = 0;
+
+        loop {
+            let mut code = 0;
+            for i in 0..code_size as usize {
+                let byte = bytes[(bit + i) / 8];
+                code |= (((byte >> ((bit + i) % 8)) & 1) as usize) << i;
+            }
+            bit += code_size as usize;
+
+            if code == clear {
+                table = reset();
+                code_size = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == end {
+                return output;
+            }
+
+            let entry = match (table.get(code), &previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(prev)) => [prev.clone(), vec![prev[0]]].concat(),
+                (None, None) => panic!("invalid code {}", code),
+            };
+            output.extend(&entry);
+
+            if let Some(prev) = previous {
+                if table.len() < 4096 {
+                    table.push([prev, vec![entry[0]]].concat());
+                }
+            }
+            if table.len() == 1 << code_size && code_size < 12 {
+                code_size += 1;
+            }
+            previous = Some(entry);
+        }
+    }
+
+    #[test]
+    fn roundtrips_lzw() {
+        let inputs: Vec<Vec<u8>> = vec![
+            vec![],
+            vec![1],
+            vec![0; 5000],
+            (0..20000u32).map(|i| ((i * 7919) % 13 % 4) as u8).collect(),
+            (0..20000u32).map(|i| ((i * i) % 251) as u8).collect(),
+        ];
+
+        for input in inputs {
+            let bits = match input.iter().max() {
+                Some(&max) if max > 3 => 8,
+                _ => 2,
+            };
+            assert_eq!(decode_lzw(&encode_lzw(&input, bits), bits), input);
+        }
+    }
+
+    #[test]
+    fn draws_sparse_grids() {
+        let mut grid = SparseGrid::default();
+        grid.insert(Point { x: 5, y: 1 }, true);
+        grid.insert(Point { x: 6, y: 2 }, false);
+
+        let frame = Frame::from_sparse(
+            &grid,
+            &Point { x: 4, y: 0 },
+            &Point { x: 6, y: 2 },
+            PALETTE,
+            |cell| match cell {
+                None => 0,
+                Some(true) => 1,
+                Some(false) => 2,
+            },
+        );
+
+        assert_eq!((frame.width, frame.height), (3, 3));
+        assert_eq!(frame.get(1, 1), 1);
+        assert_eq!(frame.get(2, 2), 2);
+        assert_eq!(frame.get(0, 0), 0);
+    }
+
+    #[test]
+    fn encodes_images() {
+        let frame = Frame::from_fn(2, 1, PALETTE, |x, _| x as u8 + 1);
+
+        let ppm = encode_ppm(&frame, 2);
+        assert!(ppm.starts_with(b"P6\n4 2\n255\n"));
+        assert_eq!(&ppm[11..17], &[255, 255, 255, 255, 255, 255]);
+        assert_eq!(&ppm[17..23], &[200, 0, 0, 200, 0, 0]);
+
+        let small = Frame::new(1, 1, PALETTE);
+        let gif = encode_gif(&[frame, small], 2, Duration::from_millis(50));
+        assert!(gif.starts_with(b"GIF89a"));
+        // canvas of the largest frame, a color table of 4 colors.
+        assert_eq!(&gif[6..11], &[4, 0, 2, 0, 0xF1]);
+        assert_eq!(&gif[13..25], &[0, 0, 0, 255, 255, 255, 200, 0, 0, 0, 0, 0]);
+        assert_eq!(gif.last(), Some(&0x3B));
+    }
+}
@@ -0,0 +1,35 @@
+//! Feeds mutated inputs to every day's parser and reports the days whose parser panics, see
+//! `template::robustness`. It runs every day's binary, so it is ignored by default:
+//! `cargo test --test parsers -- --ignored --nocapture`.
+use advent_of_code::template::commands::all::child_commands;
+
+#[test]
+#[ignore = "runs every day's binary"]
+fn parsers_do_not_panic() {
+    let flags = ["--robustness".to_string()];
+    let mut failing = vec![];
+
+    for day in 1..=25 {
+        let output = child_commands::run_with_flags(day, false, &flags, false).unwrap();
+        let panics: Vec<&String> = output.lines.iter().filter(|l| l.contains('✖')).collect();
+
+        if let Some(summary) = output.lines.iter().find(|l| l.starts_with("Robustness: ")) {
+            println!(
+                "Day {:02}: {}",
+                day,
+                summary.trim_start_matches("Robustness: ")
+            );
+        }
+        panics.iter().for_each(|l| println!("    {}", l.trim()));
+
+        if !panics.is_empty() || output.failed() {
+            failing.push(day);
+        }
+    }
+
+    assert!(
+        failing.is_empty(),
+        "the parsers of days {:?} panic or hang on mutated inputs",
+        failing
+    );
+}